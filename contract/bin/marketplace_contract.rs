@@ -15,13 +15,14 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    contracts::NamedKeys, runtime_args, CLType, CLValue, ContractHash, ContractPackageHash,
-    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs,
-    URef, U256, U512,
+    bytesrepr, contracts::NamedKeys, runtime_args, CLType, CLTyped, CLValue, ContractHash,
+    ContractPackageHash, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key,
+    Parameter, PublicKey, RuntimeArgs, Signature, URef, U256, U512,
 };
 use contract_utils::{AdminControl, ContractContext, OnChainContractStorage, ReentrancyGuard};
 use kunftmarketplace_contract::{
-    get_immediate_caller_address, Address, Marketplace, Time, TokenId,
+    get_immediate_caller_address, Address, Bids, BuyOrder, Error, FTStandard, Marketplace,
+    NFTStandard, Royalty, SellOrder, SignedOrder, Time, Token, TokenId,
 };
 
 #[derive(Default)]
@@ -58,6 +59,7 @@ pub extern "C" fn constructor() {
 pub extern "C" fn create_sell_order() {
     let caller = get_immediate_caller_address().unwrap();
     let start_time: Time = runtime::get_named_arg("start_time");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
     let collection: ContractHash = {
         let collection_str: String = runtime::get_named_arg("collection");
         ContractHash::from_formatted_str(&collection_str).unwrap()
@@ -67,11 +69,66 @@ pub extern "C" fn create_sell_order() {
         let pay_token_str: Option<String> = runtime::get_named_arg("pay_token");
         pay_token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
     };
+    let allowlist_root = merkle_root_arg("allowlist_root");
+    let reserved_buyer: Option<Address> = runtime::get_named_arg("reserved_buyer");
+    let custodial: bool = runtime::get_named_arg("custodial");
 
-    MarketplaceContract::default()
-        .create_sell_order(caller, start_time, collection, pay_token, tokens);
+    MarketplaceContract::default().create_sell_order(
+        caller,
+        start_time,
+        end_time,
+        collection,
+        pay_token,
+        tokens,
+        allowlist_root,
+        reserved_buyer,
+        custodial,
+    );
+}
+
+// hex encoded 32 byte merkle root
+fn merkle_root_arg(name: &str) -> Option<[u8; 32]> {
+    let merkle_root_str: Option<String> = runtime::get_named_arg(name);
+    merkle_root_str.map(|str| {
+        let mut root = [0u8; 32];
+        hex::decode_to_slice(str, &mut root).unwrap_or_revert_with(Error::InvalidMerkleRoot);
+        root
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn create_dutch_sell_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let start_time: Time = runtime::get_named_arg("start_time");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let pay_token: Option<ContractHash> = {
+        let pay_token_str: Option<String> = runtime::get_named_arg("pay_token");
+        pay_token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
+    };
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let start_price: U256 = runtime::get_named_arg("start_price");
+    let end_price: U256 = runtime::get_named_arg("end_price");
+    let duration: Time = runtime::get_named_arg("duration");
+
+    MarketplaceContract::default().create_dutch_sell_order(
+        caller,
+        start_time,
+        end_time,
+        collection,
+        pay_token,
+        token_id,
+        start_price,
+        end_price,
+        duration,
+    );
 }
 
+// A stale non-custodial order is invalidated and the deposit refunded, the deploy succeeds
+// without buying the token
 #[no_mangle]
 pub extern "C" fn buy_sell_order_cspr() {
     let caller = get_immediate_caller_address().unwrap();
@@ -82,6 +139,7 @@ pub extern "C" fn buy_sell_order_cspr() {
     let token_id: U256 = runtime::get_named_arg("token_id");
     let amount: U512 = runtime::get_named_arg("amount");
     let additional_recipient: Option<Address> = runtime::get_named_arg("additional_recipient");
+    let proof: Vec<(String, u8)> = runtime::get_named_arg("proof");
     MarketplaceContract::default().set_reentrancy();
     MarketplaceContract::default().buy_sell_order_cspr(
         caller,
@@ -89,10 +147,13 @@ pub extern "C" fn buy_sell_order_cspr() {
         token_id,
         amount,
         additional_recipient,
+        proof,
     );
     MarketplaceContract::default().clear_reentrancy();
 }
 
+// A stale non-custodial order is invalidated and nothing is transferred, the deploy succeeds
+// without buying the token. Reverting would roll the invalidation back
 #[no_mangle]
 pub extern "C" fn buy_sell_order() {
     let caller = get_immediate_caller_address().unwrap();
@@ -103,6 +164,7 @@ pub extern "C" fn buy_sell_order() {
     let token_id: U256 = runtime::get_named_arg("token_id");
     let amount: U256 = runtime::get_named_arg("amount");
     let additional_recipient: Option<Address> = runtime::get_named_arg("additional_recipient");
+    let proof: Vec<(String, u8)> = runtime::get_named_arg("proof");
     MarketplaceContract::default().set_reentrancy();
     MarketplaceContract::default().buy_sell_order(
         caller,
@@ -110,10 +172,200 @@ pub extern "C" fn buy_sell_order() {
         token_id,
         amount,
         additional_recipient,
+        proof,
+    );
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn create_bundle_sell_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let items: Vec<Token> = {
+        let items: Vec<(String, TokenId)> = runtime::get_named_arg("items");
+        items
+            .iter()
+            .map(|(collection_str, token_id)| {
+                (
+                    ContractHash::from_formatted_str(collection_str).unwrap(),
+                    *token_id,
+                )
+            })
+            .collect()
+    };
+    let pay_token: Option<ContractHash> = {
+        let pay_token_str: Option<String> = runtime::get_named_arg("pay_token");
+        pay_token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
+    };
+    let price: U256 = runtime::get_named_arg("price");
+    let start_time: Time = runtime::get_named_arg("start_time");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+
+    MarketplaceContract::default()
+        .create_bundle_sell_order(caller, items, pay_token, price, start_time, end_time);
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_bundle_sell_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let id: u64 = runtime::get_named_arg("id");
+    MarketplaceContract::default().cancel_bundle_sell_order(caller, id);
+}
+
+#[no_mangle]
+pub extern "C" fn buy_bundle_sell_order_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let id: u64 = runtime::get_named_arg("id");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().buy_bundle_sell_order_cspr(caller, id, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn buy_bundle_sell_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let id: u64 = runtime::get_named_arg("id");
+    let amount: U256 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().buy_bundle_sell_order(caller, id, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn buy_sell_orders_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let items: Vec<(ContractHash, TokenId, U256)> = sweep_items_arg();
+    let all_or_nothing: bool = runtime::get_named_arg("all_or_nothing");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().buy_sell_orders_cspr(caller, items, all_or_nothing, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn buy_sell_orders() {
+    let caller = get_immediate_caller_address().unwrap();
+    let items: Vec<(ContractHash, TokenId, U256)> = sweep_items_arg();
+    let pay_token: ContractHash = {
+        let pay_token_str: String = runtime::get_named_arg("pay_token");
+        ContractHash::from_formatted_str(&pay_token_str).unwrap()
+    };
+    let all_or_nothing: bool = runtime::get_named_arg("all_or_nothing");
+    let amount: U256 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().buy_sell_orders(
+        caller,
+        items,
+        pay_token,
+        all_or_nothing,
+        amount,
+    );
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+// `items` of a sweep are `(collection, token_id, max_price)`
+fn sweep_items_arg() -> Vec<(ContractHash, TokenId, U256)> {
+    let items: Vec<(String, TokenId, U256)> = runtime::get_named_arg("items");
+    items
+        .iter()
+        .map(|(collection_str, token_id, max_price)| {
+            (
+                ContractHash::from_formatted_str(collection_str).unwrap(),
+                *token_id,
+                *max_price,
+            )
+        })
+        .collect()
+}
+
+#[no_mangle]
+pub extern "C" fn fulfill_signed_order_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let order = signed_order_arg(None);
+    let public_key: PublicKey = runtime::get_named_arg("public_key");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().fulfill_signed_order_cspr(
+        caller,
+        order,
+        public_key,
+        signature_arg(),
+        amount,
     );
     MarketplaceContract::default().clear_reentrancy();
 }
 
+#[no_mangle]
+pub extern "C" fn fulfill_signed_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let pay_token: ContractHash = {
+        let pay_token_str: String = runtime::get_named_arg("pay_token");
+        ContractHash::from_formatted_str(&pay_token_str).unwrap()
+    };
+    let order = signed_order_arg(Some(pay_token));
+    let public_key: PublicKey = runtime::get_named_arg("public_key");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().fulfill_signed_order(caller, order, public_key, signature_arg());
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+fn signed_order_arg(pay_token: Option<ContractHash>) -> SignedOrder {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    SignedOrder {
+        collection,
+        token_id: runtime::get_named_arg("token_id"),
+        price: runtime::get_named_arg("price"),
+        pay_token,
+        expiry: runtime::get_named_arg("expiry"),
+        nonce: runtime::get_named_arg("nonce"),
+        epoch: runtime::get_named_arg("epoch"),
+    }
+}
+
+// `signature` is hex encoded with the leading key algorithm tag, as produced by casper-client
+fn signature_arg() -> Signature {
+    let signature_str: String = runtime::get_named_arg("signature");
+    let bytes = hex::decode(signature_str).unwrap_or_revert_with(Error::InvalidSignature);
+    bytesrepr::deserialize(bytes).unwrap_or_revert_with(Error::InvalidSignature)
+}
+
+#[no_mangle]
+pub extern "C" fn increment_order_epoch() {
+    let caller = get_immediate_caller_address().unwrap();
+    MarketplaceContract::default().increment_order_epoch(caller);
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_signed_orders() {
+    let caller = get_immediate_caller_address().unwrap();
+    let nonces: Vec<u64> = runtime::get_named_arg("nonces");
+    MarketplaceContract::default().cancel_signed_orders(caller, nonces);
+}
+
+#[no_mangle]
+pub extern "C" fn update_sell_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let price: U256 = runtime::get_named_arg("price");
+    let pay_token: Option<ContractHash> = {
+        let pay_token_str: Option<String> = runtime::get_named_arg("pay_token");
+        pay_token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
+    };
+    let start_time: Time = runtime::get_named_arg("start_time");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+
+    MarketplaceContract::default().update_sell_order(
+        caller, collection, token_id, price, pay_token, start_time, end_time,
+    );
+}
+
 #[no_mangle]
 pub extern "C" fn cancel_sell_order() {
     let caller = get_immediate_caller_address().unwrap();
@@ -125,6 +377,17 @@ pub extern "C" fn cancel_sell_order() {
     MarketplaceContract::default().cancel_sell_order(caller, collection, token_ids);
 }
 
+#[no_mangle]
+pub extern "C" fn sweep_expired_sell_orders() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_ids: Vec<TokenId> = runtime::get_named_arg("token_ids");
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().sweep_expired_sell_orders(collection, token_ids);
+}
+
 #[no_mangle]
 pub extern "C" fn create_buy_order_cspr() {
     let caller = get_immediate_caller_address().unwrap();
@@ -134,6 +397,7 @@ pub extern "C" fn create_buy_order_cspr() {
     };
     let token_id: U256 = runtime::get_named_arg("token_id");
     let additional_recipient: Option<Address> = runtime::get_named_arg("additional_recipient");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
     let amount: U512 = runtime::get_named_arg("amount");
     MarketplaceContract::default().set_reentrancy();
     MarketplaceContract::default().create_buy_order_cspr(
@@ -141,6 +405,7 @@ pub extern "C" fn create_buy_order_cspr() {
         collection,
         token_id,
         additional_recipient,
+        end_time,
         amount,
     );
     MarketplaceContract::default().clear_reentrancy();
@@ -159,6 +424,7 @@ pub extern "C" fn create_buy_order() {
         let pay_token_str: String = runtime::get_named_arg("pay_token");
         ContractHash::from_formatted_str(&pay_token_str).unwrap()
     };
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
     let amount: U256 = runtime::get_named_arg("amount");
 
     MarketplaceContract::default().create_buy_order(
@@ -167,10 +433,41 @@ pub extern "C" fn create_buy_order() {
         token_id,
         additional_recipient,
         pay_token,
+        end_time,
         amount,
     );
 }
 
+#[no_mangle]
+pub extern "C" fn update_buy_order_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let price: U256 = runtime::get_named_arg("price");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default()
+        .update_buy_order_cspr(caller, collection, token_id, price, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn update_buy_order() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let price: U256 = runtime::get_named_arg("price");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().update_buy_order(caller, collection, token_id, price);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
 #[no_mangle]
 pub extern "C" fn cancel_buy_order() {
     let caller = get_immediate_caller_address().unwrap();
@@ -184,6 +481,18 @@ pub extern "C" fn cancel_buy_order() {
     MarketplaceContract::default().clear_reentrancy();
 }
 
+#[no_mangle]
+pub extern "C" fn reclaim_expired_buy_orders() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_ids: Vec<TokenId> = runtime::get_named_arg("token_ids");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().reclaim_expired_buy_orders(collection, token_ids);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
 #[no_mangle]
 pub extern "C" fn accept_buy_order() {
     let caller = get_immediate_caller_address().unwrap();
@@ -199,55 +508,401 @@ pub extern "C" fn accept_buy_order() {
 }
 
 #[no_mangle]
-pub extern "C" fn get_deposit_purse() {
-    let purse = MarketplaceContract::default().purse();
-    // https://github.com/Jiuhong-casperlabs/restrict-access-right/blob/main/contract/src/contract.rs#L25
-    runtime::ret(CLValue::from_t(purse.into_add()).unwrap_or_revert());
+pub extern "C" fn create_collection_offer_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let quantity: u32 = runtime::get_named_arg("quantity");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+    let merkle_root = merkle_root_arg("merkle_root");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().create_collection_offer_cspr(
+        caller,
+        collection,
+        quantity,
+        end_time,
+        merkle_root,
+        amount,
+    );
+    MarketplaceContract::default().clear_reentrancy();
 }
 
 #[no_mangle]
-pub extern "C" fn set_acceptable_token() {
-    let contract_hash: ContractHash = {
-        let contract_hash_str: String = runtime::get_named_arg("contract_hash");
-        ContractHash::from_formatted_str(&contract_hash_str).unwrap()
+pub extern "C" fn create_collection_offer() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
     };
-    let fee: u32 = runtime::get_named_arg("fee");
-    MarketplaceContract::default().assert_caller_is_admin();
-    MarketplaceContract::default().set_acceptable_token(contract_hash, fee);
+    let pay_token: ContractHash = {
+        let pay_token_str: String = runtime::get_named_arg("pay_token");
+        ContractHash::from_formatted_str(&pay_token_str).unwrap()
+    };
+    let price: U256 = runtime::get_named_arg("price");
+    let quantity: u32 = runtime::get_named_arg("quantity");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+    let merkle_root = merkle_root_arg("merkle_root");
+
+    MarketplaceContract::default().create_collection_offer(
+        caller,
+        collection,
+        pay_token,
+        price,
+        quantity,
+        end_time,
+        merkle_root,
+    );
 }
 
 #[no_mangle]
-pub extern "C" fn remove_acceptable_token() {
-    let contract_hash: ContractHash = {
-        let contract_hash_str: String = runtime::get_named_arg("contract_hash");
-        ContractHash::from_formatted_str(&contract_hash_str).unwrap()
+pub extern "C" fn cancel_collection_offer() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
     };
-    MarketplaceContract::default().assert_caller_is_admin();
-    MarketplaceContract::default().remove_acceptable_token(contract_hash);
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().cancel_collection_offer(caller, collection);
+    MarketplaceContract::default().clear_reentrancy();
 }
 
 #[no_mangle]
-pub extern "C" fn set_fee_wallet() {
-    let fee_wallet: Address = runtime::get_named_arg("fee_wallet");
-    MarketplaceContract::default().assert_caller_is_admin();
-    MarketplaceContract::default().set_fee_wallet(fee_wallet);
+pub extern "C" fn accept_collection_offer() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let bidder: Address = runtime::get_named_arg("bidder");
+    let proof: Vec<(String, u8)> = runtime::get_named_arg("proof");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default()
+        .accept_collection_offer(caller, collection, token_id, bidder, proof);
+    MarketplaceContract::default().clear_reentrancy();
 }
 
 #[no_mangle]
-pub extern "C" fn call() {
-    let contract_name: String = runtime::get_named_arg("contract_name");
-    let acceptable_tokens: BTreeMap<String, u32> = runtime::get_named_arg("acceptable_tokens");
-    let fee_wallet: Address = runtime::get_named_arg("fee_wallet");
-    let exist_contract_package_hash: Option<ContractPackageHash> = {
-        let contract_package_hash_str: Option<String> =
-            runtime::get_named_arg("contract_package_hash");
-        contract_package_hash_str.map(|str| ContractPackageHash::from_formatted_str(&str).unwrap())
+pub extern "C" fn create_auction() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
     };
-    let (contract_hash, _) = match exist_contract_package_hash {
-        Some(contract_package_hash) => {
-            let named_keys = NamedKeys::new();
-
-            storage::add_contract_version(contract_package_hash, get_entry_points(), named_keys)
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let pay_token: Option<ContractHash> = {
+        let pay_token_str: Option<String> = runtime::get_named_arg("pay_token");
+        pay_token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
+    };
+    let reserve_price: U256 = runtime::get_named_arg("reserve_price");
+    let min_bid_increment: U256 = runtime::get_named_arg("min_bid_increment");
+    let start_time: Time = runtime::get_named_arg("start_time");
+    let end_time: Time = runtime::get_named_arg("end_time");
+    let extension_window: Time = runtime::get_named_arg("extension_window");
+    MarketplaceContract::default().create_auction(
+        caller,
+        collection,
+        token_id,
+        pay_token,
+        reserve_price,
+        min_bid_increment,
+        start_time,
+        end_time,
+        extension_window,
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn place_bid_cspr() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let amount: U512 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().place_bid_cspr(caller, collection, token_id, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn place_bid() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    let amount: U256 = runtime::get_named_arg("amount");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().place_bid(caller, collection, token_id, amount);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn settle_auction() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    MarketplaceContract::default().set_reentrancy();
+    MarketplaceContract::default().settle_auction(collection, token_id);
+    MarketplaceContract::default().clear_reentrancy();
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_auction() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: U256 = runtime::get_named_arg("token_id");
+    MarketplaceContract::default().cancel_auction(caller, collection, token_id);
+}
+
+#[no_mangle]
+pub extern "C" fn get_deposit_purse() {
+    let purse = MarketplaceContract::default().purse();
+    // https://github.com/Jiuhong-casperlabs/restrict-access-right/blob/main/contract/src/contract.rs#L25
+    runtime::ret(CLValue::from_t(purse.into_add()).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_sell_order() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let order = MarketplaceContract::default().sell_order(collection, token_id);
+    runtime::ret(CLValue::from_t(order).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_sell_order_by_id() {
+    let id: u64 = runtime::get_named_arg("id");
+    let order = MarketplaceContract::default().sell_order_by_id(id);
+    runtime::ret(CLValue::from_t(order).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_buy_orders() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let token_id: TokenId = runtime::get_named_arg("token_id");
+    let bids = MarketplaceContract::default().buy_orders(collection, token_id);
+    runtime::ret(CLValue::from_t(bids).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_fee() {
+    let token = token_arg();
+    let fee = MarketplaceContract::default().fee(token);
+    runtime::ret(CLValue::from_t(fee).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_fee_wallet() {
+    let wallet = MarketplaceContract::default().fee_wallet();
+    runtime::ret(CLValue::from_t(wallet).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn is_acceptable_token() {
+    let token = token_arg();
+    let acceptable = MarketplaceContract::default().is_acceptable_token(token);
+    runtime::ret(CLValue::from_t(acceptable).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn list_acceptable_tokens() {
+    let tokens = MarketplaceContract::default().acceptable_tokens();
+    runtime::ret(CLValue::from_t(tokens).unwrap_or_revert());
+}
+
+// Pages the pending fixed-price and dutch sell orders of `seller`. Bundles, auctions and
+// collection offers are not indexed
+#[no_mangle]
+pub extern "C" fn get_sell_orders_by_seller() {
+    let seller: Address = runtime::get_named_arg("seller");
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+    let orders = MarketplaceContract::default().sell_orders_by_seller(seller, offset, limit);
+    runtime::ret(CLValue::from_t(orders).unwrap_or_revert());
+}
+
+// Pages the pending fixed-price and dutch sell orders of `collection`. Bundles and auctions are
+// not indexed
+#[no_mangle]
+pub extern "C" fn get_sell_orders_by_collection() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+    let orders =
+        MarketplaceContract::default().sell_orders_by_collection(collection, offset, limit);
+    runtime::ret(CLValue::from_t(orders).unwrap_or_revert());
+}
+
+// Pages the pending per-token bids of `bidder`. Collection offers and auction bids are not
+// indexed
+#[no_mangle]
+pub extern "C" fn get_buy_orders_by_bidder() {
+    let bidder: Address = runtime::get_named_arg("bidder");
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+    let bids = MarketplaceContract::default().buy_orders_by_bidder(bidder, offset, limit);
+    runtime::ret(CLValue::from_t(bids).unwrap_or_revert());
+}
+
+// `token` is a formatted contract hash, `None` refers to CSPR
+fn token_arg() -> Option<ContractHash> {
+    let token_str: Option<String> = runtime::get_named_arg("token");
+    token_str.map(|str| ContractHash::from_formatted_str(&str).unwrap())
+}
+
+#[no_mangle]
+pub extern "C" fn set_acceptable_token() {
+    let contract_hash: ContractHash = {
+        let contract_hash_str: String = runtime::get_named_arg("contract_hash");
+        ContractHash::from_formatted_str(&contract_hash_str).unwrap()
+    };
+    let fee: u32 = runtime::get_named_arg("fee");
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_acceptable_token(contract_hash, fee);
+}
+
+#[no_mangle]
+pub extern "C" fn remove_acceptable_token() {
+    let contract_hash: ContractHash = {
+        let contract_hash_str: String = runtime::get_named_arg("contract_hash");
+        ContractHash::from_formatted_str(&contract_hash_str).unwrap()
+    };
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().remove_acceptable_token(contract_hash);
+}
+
+#[no_mangle]
+pub extern "C" fn set_token_standard() {
+    let contract_hash: ContractHash = {
+        let contract_hash_str: String = runtime::get_named_arg("contract_hash");
+        ContractHash::from_formatted_str(&contract_hash_str).unwrap()
+    };
+    let standard: FTStandard = {
+        let standard: u8 = runtime::get_named_arg("standard");
+        FTStandard::from_u8(standard).unwrap_or_revert()
+    };
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_token_standard(contract_hash, standard);
+}
+
+#[no_mangle]
+pub extern "C" fn set_fee_wallet() {
+    let fee_wallet: Address = runtime::get_named_arg("fee_wallet");
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_fee_wallet(fee_wallet);
+}
+
+#[no_mangle]
+pub extern "C" fn set_collection_admin() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let admin: Address = runtime::get_named_arg("admin");
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_collection_admin(collection, admin);
+}
+
+#[no_mangle]
+pub extern "C" fn set_collection_standard() {
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let standard: NFTStandard = {
+        let standard: u8 = runtime::get_named_arg("standard");
+        NFTStandard::from_u8(standard).unwrap_or_revert()
+    };
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_collection_standard(collection, standard);
+}
+
+#[no_mangle]
+pub extern "C" fn set_royalty() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let shares: Vec<Royalty> = {
+        let shares: Vec<(Address, u32)> = runtime::get_named_arg("shares");
+        shares
+            .iter()
+            .map(|(recipient, bps)| Royalty {
+                recipient: *recipient,
+                bps: *bps,
+            })
+            .collect()
+    };
+    let is_collection_admin = MarketplaceContract::default()
+        .collection_admin(collection)
+        .map_or(false, |admin| admin.eq(&caller));
+    if !is_collection_admin {
+        MarketplaceContract::default().assert_caller_is_admin();
+    }
+    MarketplaceContract::default().set_royalty(collection, shares);
+}
+
+#[no_mangle]
+pub extern "C" fn set_royalty_info_supported() {
+    let caller = get_immediate_caller_address().unwrap();
+    let collection: ContractHash = {
+        let collection_str: String = runtime::get_named_arg("collection");
+        ContractHash::from_formatted_str(&collection_str).unwrap()
+    };
+    let supported: bool = runtime::get_named_arg("supported");
+    let is_collection_admin = MarketplaceContract::default()
+        .collection_admin(collection)
+        .map_or(false, |admin| admin.eq(&caller));
+    if !is_collection_admin {
+        MarketplaceContract::default().assert_caller_is_admin();
+    }
+    MarketplaceContract::default().set_royalty_info_supported(collection, supported);
+}
+
+#[no_mangle]
+pub extern "C" fn set_max_auction_extension() {
+    let window: Time = runtime::get_named_arg("window");
+    MarketplaceContract::default().assert_caller_is_admin();
+    MarketplaceContract::default().set_max_auction_extension(window);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    let acceptable_tokens: BTreeMap<String, u32> = runtime::get_named_arg("acceptable_tokens");
+    let fee_wallet: Address = runtime::get_named_arg("fee_wallet");
+    let exist_contract_package_hash: Option<ContractPackageHash> = {
+        let contract_package_hash_str: Option<String> =
+            runtime::get_named_arg("contract_package_hash");
+        contract_package_hash_str.map(|str| ContractPackageHash::from_formatted_str(&str).unwrap())
+    };
+    let (contract_hash, _) = match exist_contract_package_hash {
+        Some(contract_package_hash) => {
+            let named_keys = NamedKeys::new();
+
+            storage::add_contract_version(contract_package_hash, get_entry_points(), named_keys)
         }
         None => storage::new_contract(
             get_entry_points(),
@@ -316,6 +971,7 @@ fn get_entry_points() -> EntryPoints {
         "create_sell_order",
         vec![
             Parameter::new("start_time", CLType::U64),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
             Parameter::new("collection", CLType::String),
             Parameter::new(
                 "tokens",
@@ -324,6 +980,10 @@ fn get_entry_points() -> EntryPoints {
                     value: Box::new(CLType::U256),
                 },
             ),
+            Parameter::new("pay_token", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("allowlist_root", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("reserved_buyer", CLType::Option(Box::new(CLType::Key))),
+            Parameter::new("custodial", CLType::Bool),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -331,15 +991,16 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "buy_sell_order_cspr",
+        "create_dutch_sell_order",
         vec![
+            Parameter::new("start_time", CLType::U64),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
             Parameter::new("collection", CLType::String),
+            Parameter::new("pay_token", CLType::Option(Box::new(CLType::String))),
             Parameter::new("token_id", CLType::U256),
-            Parameter::new("amount", CLType::U512),
-            Parameter::new(
-                "additional_recipient",
-                CLType::Option(Box::new(CLType::Key)),
-            ),
+            Parameter::new("start_price", CLType::U256),
+            Parameter::new("end_price", CLType::U256),
+            Parameter::new("duration", CLType::U64),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -347,7 +1008,7 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "buy_sell_order",
+        "buy_sell_order_cspr",
         vec![
             Parameter::new("collection", CLType::String),
             Parameter::new("token_id", CLType::U256),
@@ -356,6 +1017,13 @@ fn get_entry_points() -> EntryPoints {
                 "additional_recipient",
                 CLType::Option(Box::new(CLType::Key)),
             ),
+            Parameter::new(
+                "proof",
+                CLType::List(Box::new(CLType::Tuple2([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U8),
+                ]))),
+            ),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -363,27 +1031,42 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "cancel_sell_order",
+        "buy_sell_order",
         vec![
             Parameter::new("collection", CLType::String),
             Parameter::new("token_id", CLType::U256),
+            Parameter::new("amount", CLType::U256),
+            Parameter::new(
+                "additional_recipient",
+                CLType::Option(Box::new(CLType::Key)),
+            ),
+            Parameter::new(
+                "proof",
+                CLType::List(Box::new(CLType::Tuple2([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U8),
+                ]))),
+            ),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
-    //
     entry_points.add_entry_point(EntryPoint::new(
-        "create_buy_order_cspr",
+        "create_bundle_sell_order",
         vec![
-            Parameter::new("collection", CLType::String),
-            Parameter::new("token_id", CLType::U256),
             Parameter::new(
-                "additional_recipient",
-                CLType::Option(Box::new(CLType::Key)),
+                "items",
+                CLType::List(Box::new(CLType::Tuple2([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U256),
+                ]))),
             ),
-            Parameter::new("amount", CLType::U512),
+            Parameter::new("pay_token", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("start_time", CLType::U64),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -391,26 +1074,18 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "create_buy_order",
-        vec![
-            Parameter::new("collection", CLType::String),
-            Parameter::new("token_id", CLType::U256),
-            Parameter::new(
-                "additional_recipient",
-                CLType::Option(Box::new(CLType::Key)),
-            ),
-            Parameter::new("pay_token", CLType::String),
-            Parameter::new("amount", CLType::U256),
-        ],
+        "cancel_bundle_sell_order",
+        vec![Parameter::new("id", CLType::U64)],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+
     entry_points.add_entry_point(EntryPoint::new(
-        "cancel_buy_order",
+        "buy_bundle_sell_order_cspr",
         vec![
-            Parameter::new("collection", CLType::String),
-            Parameter::new("token_id", CLType::U256),
+            Parameter::new("id", CLType::U64),
+            Parameter::new("amount", CLType::U512),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -418,11 +1093,10 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "accept_buy_order",
+        "buy_bundle_sell_order",
         vec![
-            Parameter::new("collection", CLType::String),
-            Parameter::new("token_id", CLType::U256),
-            Parameter::new("bidder", CLType::Key),
+            Parameter::new("id", CLType::U64),
+            Parameter::new("amount", CLType::U256),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -430,10 +1104,18 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "set_acceptable_token",
+        "buy_sell_orders_cspr",
         vec![
-            Parameter::new("contract_hash", CLType::String),
-            Parameter::new("fee", CLType::U32),
+            Parameter::new(
+                "items",
+                CLType::List(Box::new(CLType::Tuple3([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U256),
+                    Box::new(CLType::U256),
+                ]))),
+            ),
+            Parameter::new("all_or_nothing", CLType::Bool),
+            Parameter::new("amount", CLType::U512),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -441,13 +1123,360 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "remove_acceptable_token",
+        "buy_sell_orders",
+        vec![
+            Parameter::new(
+                "items",
+                CLType::List(Box::new(CLType::Tuple3([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U256),
+                    Box::new(CLType::U256),
+                ]))),
+            ),
+            Parameter::new("pay_token", CLType::String),
+            Parameter::new("all_or_nothing", CLType::Bool),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "fulfill_signed_order_cspr",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("expiry", CLType::U64),
+            Parameter::new("nonce", CLType::U64),
+            Parameter::new("epoch", CLType::U64),
+            Parameter::new("public_key", CLType::PublicKey),
+            Parameter::new("signature", CLType::String),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "fulfill_signed_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("pay_token", CLType::String),
+            Parameter::new("expiry", CLType::U64),
+            Parameter::new("nonce", CLType::U64),
+            Parameter::new("epoch", CLType::U64),
+            Parameter::new("public_key", CLType::PublicKey),
+            Parameter::new("signature", CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "increment_order_epoch",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_signed_orders",
+        vec![Parameter::new(
+            "nonces",
+            CLType::List(Box::new(CLType::U64)),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_sell_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("pay_token", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("start_time", CLType::U64),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_sell_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_ids", CLType::List(Box::new(CLType::U256))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "sweep_expired_sell_orders",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_ids", CLType::List(Box::new(CLType::U256))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    //
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_buy_order_cspr",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new(
+                "additional_recipient",
+                CLType::Option(Box::new(CLType::Key)),
+            ),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_buy_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new(
+                "additional_recipient",
+                CLType::Option(Box::new(CLType::Key)),
+            ),
+            Parameter::new("pay_token", CLType::String),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_buy_order_cspr",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_buy_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("price", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_buy_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "reclaim_expired_buy_orders",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_ids", CLType::List(Box::new(CLType::U256))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_buy_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("bidder", CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_collection_offer_cspr",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("quantity", CLType::U32),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
+            Parameter::new("merkle_root", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_collection_offer",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("pay_token", CLType::String),
+            Parameter::new("price", CLType::U256),
+            Parameter::new("quantity", CLType::U32),
+            Parameter::new("end_time", CLType::Option(Box::new(CLType::U64))),
+            Parameter::new("merkle_root", CLType::Option(Box::new(CLType::String))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_collection_offer",
+        vec![Parameter::new("collection", CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_collection_offer",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("bidder", CLType::Key),
+            Parameter::new(
+                "proof",
+                CLType::List(Box::new(CLType::Tuple2([
+                    Box::new(CLType::String),
+                    Box::new(CLType::U8),
+                ]))),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_auction",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("pay_token", CLType::Option(Box::new(CLType::String))),
+            Parameter::new("reserve_price", CLType::U256),
+            Parameter::new("min_bid_increment", CLType::U256),
+            Parameter::new("start_time", CLType::U64),
+            Parameter::new("end_time", CLType::U64),
+            Parameter::new("extension_window", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "place_bid_cspr",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "place_bid",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "settle_auction",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_auction",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_acceptable_token",
+        vec![
+            Parameter::new("contract_hash", CLType::String),
+            Parameter::new("fee", CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_acceptable_token",
         vec![Parameter::new("contract_hash", CLType::String)],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_token_standard",
+        vec![
+            Parameter::new("contract_hash", CLType::String),
+            Parameter::new("standard", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
     entry_points.add_entry_point(EntryPoint::new(
         "set_fee_wallet",
         vec![Parameter::new("fee_wallet", CLType::Key)],
@@ -456,6 +1485,168 @@ fn get_entry_points() -> EntryPoints {
         EntryPointType::Contract,
     ));
 
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_collection_admin",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("admin", CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_collection_standard",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("standard", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_royalty",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new(
+                "shares",
+                CLType::List(Box::new(CLType::Tuple2([
+                    Box::new(CLType::Key),
+                    Box::new(CLType::U32),
+                ]))),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_royalty_info_supported",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("supported", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_max_auction_extension",
+        vec![Parameter::new("window", CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_sell_order",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+        ],
+        SellOrder::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_sell_order_by_id",
+        vec![Parameter::new("id", CLType::U64)],
+        SellOrder::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_buy_orders",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("token_id", CLType::U256),
+        ],
+        Bids::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_fee",
+        vec![Parameter::new(
+            "token",
+            CLType::Option(Box::new(CLType::String)),
+        )],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_fee_wallet",
+        vec![],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_acceptable_token",
+        vec![Parameter::new(
+            "token",
+            CLType::Option(Box::new(CLType::String)),
+        )],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "list_acceptable_tokens",
+        vec![],
+        Vec::<ContractHash>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_sell_orders_by_seller",
+        vec![
+            Parameter::new("seller", CLType::Key),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        Vec::<SellOrder>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_sell_orders_by_collection",
+        vec![
+            Parameter::new("collection", CLType::String),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        Vec::<SellOrder>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_buy_orders_by_bidder",
+        vec![
+            Parameter::new("bidder", CLType::Key),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        Vec::<(Token, BuyOrder)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
     entry_points.add_entry_point(EntryPoint::new(
         "get_deposit_purse",
         vec![],
@@ -1,7 +1,7 @@
 #![no_main]
 #![no_std]
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use casper_contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
@@ -21,6 +21,7 @@ pub extern "C" fn call() {
     let token_id: U256 = runtime::get_named_arg("token_id");
     let amount: U512 = runtime::get_named_arg("amount");
     let additional_recipient: Option<Address> = runtime::get_named_arg("additional_recipient");
+    let proof: Vec<(String, u8)> = runtime::get_named_arg("proof");
     let deposit_purse: URef =
         runtime::call_contract(marketplace_contract, "get_deposit_purse", runtime_args! {});
     let account_purse = account::get_main_purse();
@@ -33,7 +34,8 @@ pub extern "C" fn call() {
           "collection" => collection,
           "token_id" => token_id,
           "amount" => amount,
-          "additional_recipient" => additional_recipient
+          "additional_recipient" => additional_recipient,
+          "proof" => proof
         },
     );
 }
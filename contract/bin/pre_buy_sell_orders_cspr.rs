@@ -0,0 +1,38 @@
+#![no_main]
+#![no_std]
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, URef, U256, U512};
+
+extern crate alloc;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let marketplace_contract: ContractHash = {
+        let marketplace_contract_str: String = runtime::get_named_arg("marketplace_contract");
+        ContractHash::from_formatted_str(&marketplace_contract_str).unwrap()
+    };
+    // `(collection, token_id, max_price)` of every order to buy
+    let items: Vec<(String, U256, U256)> = runtime::get_named_arg("items");
+    let all_or_nothing: bool = runtime::get_named_arg("all_or_nothing");
+    // the total deposited once for the whole sweep, the unspent remainder is refunded
+    let amount: U512 = runtime::get_named_arg("amount");
+    let deposit_purse: URef =
+        runtime::call_contract(marketplace_contract, "get_deposit_purse", runtime_args! {});
+    let account_purse = account::get_main_purse();
+    system::transfer_from_purse_to_purse(account_purse, deposit_purse, amount, None)
+        .unwrap_or_revert();
+    let _: () = runtime::call_contract(
+        marketplace_contract,
+        "buy_sell_orders_cspr",
+        runtime_args! {
+          "items" => items,
+          "all_or_nothing" => all_or_nothing,
+          "amount" => amount
+        },
+    );
+}
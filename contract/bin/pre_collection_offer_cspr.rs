@@ -0,0 +1,42 @@
+#![no_main]
+#![no_std]
+
+use alloc::string::String;
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, URef, U512};
+use kunftmarketplace_contract::Time;
+
+extern crate alloc;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let marketplace_contract: ContractHash = {
+        let marketplace_contract_str: String = runtime::get_named_arg("marketplace_contract");
+        ContractHash::from_formatted_str(&marketplace_contract_str).unwrap()
+    };
+    let collection: String = runtime::get_named_arg("collection");
+    let quantity: u32 = runtime::get_named_arg("quantity");
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
+    let merkle_root: Option<String> = runtime::get_named_arg("merkle_root");
+    // `amount` is the total escrow, the price of each token is `amount / quantity`
+    let amount: U512 = runtime::get_named_arg("amount");
+    let deposit_purse: URef =
+        runtime::call_contract(marketplace_contract, "get_deposit_purse", runtime_args! {});
+    let account_purse = account::get_main_purse();
+    system::transfer_from_purse_to_purse(account_purse, deposit_purse, amount, None)
+        .unwrap_or_revert();
+    let _: () = runtime::call_contract(
+        marketplace_contract,
+        "create_collection_offer_cspr",
+        runtime_args! {
+          "collection" => collection,
+          "quantity" => quantity,
+          "end_time" => end_time,
+          "merkle_root" => merkle_root,
+          "amount" => amount
+        },
+    );
+}
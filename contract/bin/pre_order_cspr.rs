@@ -7,7 +7,7 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{runtime_args, ContractHash, RuntimeArgs, URef, U256, U512};
-use kunftmarketplace_contract::Address;
+use kunftmarketplace_contract::{Address, Time};
 
 extern crate alloc;
 
@@ -18,11 +18,14 @@ pub extern "C" fn call() {
         ContractHash::from_formatted_str(&marketplace_contract_str).unwrap()
     };
     let entrypoint: String = runtime::get_named_arg("entrypoint");
-    // acceptable entrypoint name is `buy_sell_order_cspr` `create_buy_order_cspr`
+    // acceptable entrypoint name is `create_buy_order_cspr` `place_bid_cspr`, sell orders are
+    // bought through `pre_buy_sell_order_cspr` which also forwards the allowlist proof
     let collection: String = runtime::get_named_arg("collection");
     let token_id: U256 = runtime::get_named_arg("token_id");
     let amount: U512 = runtime::get_named_arg("amount");
     let additional_recipient: Option<Address> = runtime::get_named_arg("additional_recipient");
+    // only used by `create_buy_order_cspr`
+    let end_time: Option<Time> = runtime::get_named_arg("end_time");
     let deposit_purse: URef =
         runtime::call_contract(marketplace_contract, "get_deposit_purse", runtime_args! {});
     let account_purse = account::get_main_purse();
@@ -35,7 +38,8 @@ pub extern "C" fn call() {
           "collection" => collection,
           "token_id" => token_id,
           "amount" => amount,
-          "additional_recipient" => additional_recipient
+          "additional_recipient" => additional_recipient,
+          "end_time" => end_time
         },
     );
 }
@@ -9,11 +9,23 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    bytesrepr::ToBytes, CLTyped, ContractHash, ContractPackageHash, Key, URef, U512,
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, ContractHash, ContractPackageHash, Key, URef, U512,
 };
 use contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
+use core::marker::PhantomData;
 
-use crate::{event::MarketplaceEvent, structs::order::SellOrder, Address, Bids, Error, TokenId};
+use crate::{
+    enums::{FTStandard, NFTStandard},
+    event::MarketplaceEvent,
+    structs::{
+        auction::Auction,
+        bundle::Bundle,
+        order::{BuyOrder, CollectionOffer, SellOrder},
+        royalty::Royalty,
+    },
+    Address, Bids, Error, OrderStatus, Time, Token, TokenId,
+};
 
 fn contract_hash_and_value_to_str<T: ToBytes + CLTyped>(
     contract_hash: ContractHash,
@@ -22,65 +34,191 @@ fn contract_hash_and_value_to_str<T: ToBytes + CLTyped>(
     key_and_value_to_str(&Key::from(contract_hash), &value)
 }
 
-const SELL_ORDERS_DICT: &str = "sell_orders";
+const SELL_ORDERS_DICT: &str = "sell_orders_by_id";
+const ACTIVE_SELL_ORDERS_DICT: &str = "active_sell_orders";
 
+// Sell orders are kept by id, `active` points each listed token to its pending order
 pub struct SellOrders {
     dict: Dict,
+    active: Dict,
 }
 
 impl SellOrders {
     pub fn instance() -> SellOrders {
         SellOrders {
             dict: Dict::instance(SELL_ORDERS_DICT),
+            active: Dict::instance(ACTIVE_SELL_ORDERS_DICT),
         }
     }
 
     pub fn init() {
         Dict::init(SELL_ORDERS_DICT);
+        Dict::init(ACTIVE_SELL_ORDERS_DICT);
     }
 
-    fn contract_hash_and_value_to_str(
-        &self,
-        contract_hash: ContractHash,
-        created_time: TokenId,
-    ) -> String {
-        key_and_value_to_str(&Key::from(contract_hash), &created_time)
+    pub fn get(&self, contract_hash: ContractHash, token_id: TokenId) -> SellOrder {
+        self.try_get(contract_hash, token_id)
+            .unwrap_or_revert_with(Error::NotExistOrder)
     }
 
-    pub fn get(&self, contract_hash: ContractHash, token_id: TokenId) -> SellOrder {
+    pub fn try_get(&self, contract_hash: ContractHash, token_id: TokenId) -> Option<SellOrder> {
+        let id = self.active_id(contract_hash, token_id)?;
+        self.dict.get(&id.to_string())
+    }
+
+    pub fn get_by_id(&self, id: u64) -> SellOrder {
         self.dict
-            .get(&self.contract_hash_and_value_to_str(contract_hash, token_id))
+            .get(&id.to_string())
             .unwrap_or_revert_with(Error::NotExistOrder)
     }
 
-    pub fn set(&self, contract_hash: ContractHash, token_id: TokenId, order: SellOrder) {
-        self.dict.set(
-            &self.contract_hash_and_value_to_str(contract_hash, token_id),
-            order,
-        );
+    pub fn active_id(&self, contract_hash: ContractHash, token_id: TokenId) -> Option<u64> {
+        self.active
+            .get(&contract_hash_and_value_to_str(contract_hash, token_id))
     }
 
-    pub fn remove(&self, contract_hash: ContractHash, token_id: TokenId) {
-        self.dict
-            .remove::<SellOrder>(&self.contract_hash_and_value_to_str(contract_hash, token_id));
+    // Stores the order and keeps it as the active order of its token while pending
+    pub fn set(&self, order: SellOrder) {
+        self.dict.set(&order.id.to_string(), order);
+        let key = contract_hash_and_value_to_str(order.collection, order.token_id);
+        if order.status == OrderStatus::Pending {
+            self.active.set(&key, order.id);
+            OrderIndex::sellers().add(Key::from(order.creator), order.id);
+            OrderIndex::collections().add(Key::from(order.collection), order.id);
+        } else {
+            self.active.remove::<u64>(&key);
+            OrderIndex::sellers().remove(Key::from(order.creator), order.id);
+            OrderIndex::collections().remove(Key::from(order.collection), order.id);
+        }
+    }
+}
+
+const SELLER_ORDERS_INDEX: &str = "seller_orders";
+const COLLECTION_ORDERS_INDEX: &str = "collection_orders";
+const BIDDER_ORDERS_INDEX: &str = "bidder_orders";
+
+// Enumerable set of order keys per owner: a count, index -> item, and item -> index so that a
+// removed item can be swapped with the last one
+pub struct OrderIndex<T> {
+    counts: Dict,
+    items: Dict,
+    positions: Dict,
+    item: PhantomData<T>,
+}
+
+impl OrderIndex<u64> {
+    // pending sell order ids by creator
+    pub fn sellers() -> OrderIndex<u64> {
+        OrderIndex::instance(SELLER_ORDERS_INDEX)
+    }
+
+    // pending sell order ids by collection
+    pub fn collections() -> OrderIndex<u64> {
+        OrderIndex::instance(COLLECTION_ORDERS_INDEX)
+    }
+}
+
+impl OrderIndex<Token> {
+    // tokens with a pending buy order by bidder
+    pub fn bidders() -> OrderIndex<Token> {
+        OrderIndex::instance(BIDDER_ORDERS_INDEX)
     }
 }
 
+impl<T: CLTyped + ToBytes + FromBytes> OrderIndex<T> {
+    fn instance(name: &str) -> OrderIndex<T> {
+        OrderIndex {
+            counts: Dict::instance(&format!("{}_counts", name)),
+            items: Dict::instance(&format!("{}_items", name)),
+            positions: Dict::instance(&format!("{}_positions", name)),
+            item: PhantomData,
+        }
+    }
+
+    pub fn count(&self, owner: Key) -> u64 {
+        self.counts.get_by_key(&owner).unwrap_or_default()
+    }
+
+    pub fn get(&self, owner: Key, index: u64) -> Option<T> {
+        self.items.get(&key_and_value_to_str(&owner, &index))
+    }
+
+    pub fn page(&self, owner: Key, offset: u64, limit: u64) -> Vec<T> {
+        let end = offset.saturating_add(limit).min(self.count(owner));
+        (offset..end)
+            .filter_map(|index| self.get(owner, index))
+            .collect()
+    }
+
+    pub fn add(&self, owner: Key, item: T) {
+        let position_key = key_and_value_to_str(&owner, &item);
+        if self.positions.get::<u64>(&position_key).is_some() {
+            return;
+        }
+        let count = self.count(owner);
+        self.items.set(&key_and_value_to_str(&owner, &count), item);
+        self.positions.set(&position_key, count);
+        self.counts.set_by_key(&owner, count + 1);
+    }
+
+    pub fn remove(&self, owner: Key, item: T) {
+        let position_key = key_and_value_to_str(&owner, &item);
+        let position = match self.positions.get::<u64>(&position_key) {
+            Some(position) => position,
+            None => return,
+        };
+        let last = self.count(owner) - 1;
+        if position != last {
+            let last_item: T = self.get(owner, last).unwrap_or_revert();
+            self.positions
+                .set(&key_and_value_to_str(&owner, &last_item), position);
+            self.items
+                .set(&key_and_value_to_str(&owner, &position), last_item);
+        }
+        self.items.remove::<T>(&key_and_value_to_str(&owner, &last));
+        self.positions.remove::<u64>(&position_key);
+        self.counts.set_by_key(&owner, last);
+    }
+}
+
+pub fn init_order_indexes() {
+    [
+        SELLER_ORDERS_INDEX,
+        COLLECTION_ORDERS_INDEX,
+        BIDDER_ORDERS_INDEX,
+    ]
+    .iter()
+    .for_each(|name| {
+        Dict::init(&format!("{}_counts", name));
+        Dict::init(&format!("{}_items", name));
+        Dict::init(&format!("{}_positions", name));
+    });
+}
+
 const BUY_ORDERS_DICT: &str = "buy_orders";
+const CLOSED_BUY_ORDERS_DICT: &str = "closed_buy_orders";
 
+// Pending bids are grouped by token, closed ones are kept by id
 pub struct BuyOrders {
     dict: Dict,
+    closed: Dict,
 }
 
 impl BuyOrders {
     pub fn instance() -> BuyOrders {
         BuyOrders {
             dict: Dict::instance(BUY_ORDERS_DICT),
+            closed: Dict::instance(CLOSED_BUY_ORDERS_DICT),
         }
     }
 
     pub fn init() {
         Dict::init(BUY_ORDERS_DICT);
+        Dict::init(CLOSED_BUY_ORDERS_DICT);
+    }
+
+    pub fn set_closed(&self, bid: BuyOrder) {
+        self.closed.set(&bid.id.to_string(), bid);
     }
 
     pub fn get(&self, contract_hash: ContractHash, token_id: TokenId) -> Bids {
@@ -102,6 +240,185 @@ impl BuyOrders {
     }
 }
 
+const COLLECTION_OFFERS_DICT: &str = "collection_offers";
+const CLOSED_COLLECTION_OFFERS_DICT: &str = "closed_collection_offers";
+
+// Open offers are keyed by collection and bidder, filled and canceled ones are kept by id
+pub struct CollectionOffers {
+    dict: Dict,
+    closed: Dict,
+}
+
+impl CollectionOffers {
+    pub fn instance() -> CollectionOffers {
+        CollectionOffers {
+            dict: Dict::instance(COLLECTION_OFFERS_DICT),
+            closed: Dict::instance(CLOSED_COLLECTION_OFFERS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_OFFERS_DICT);
+        Dict::init(CLOSED_COLLECTION_OFFERS_DICT);
+    }
+
+    // Moves the offer out of the open ones once its status is final
+    pub fn set_closed(&self, contract_hash: ContractHash, bidder: Address, offer: CollectionOffer) {
+        self.remove(contract_hash, bidder);
+        self.closed.set(&offer.id.to_string(), offer);
+    }
+
+    pub fn get(&self, contract_hash: ContractHash, bidder: Address) -> Option<CollectionOffer> {
+        self.dict
+            .get(&contract_hash_and_value_to_str(contract_hash, bidder))
+    }
+
+    pub fn set(&self, contract_hash: ContractHash, bidder: Address, offer: CollectionOffer) {
+        self.dict.set(
+            &contract_hash_and_value_to_str(contract_hash, bidder),
+            offer,
+        );
+    }
+
+    pub fn remove(&self, contract_hash: ContractHash, bidder: Address) {
+        self.dict
+            .remove::<CollectionOffer>(&contract_hash_and_value_to_str(contract_hash, bidder));
+    }
+}
+
+const BUNDLES_DICT: &str = "bundles";
+
+// Bundles are kept by id in every status
+pub struct Bundles {
+    dict: Dict,
+}
+
+impl Bundles {
+    pub fn instance() -> Bundles {
+        Bundles {
+            dict: Dict::instance(BUNDLES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BUNDLES_DICT);
+    }
+
+    pub fn get(&self, id: u64) -> Bundle {
+        self.dict
+            .get(&id.to_string())
+            .unwrap_or_revert_with(Error::NotExistOrder)
+    }
+
+    pub fn set(&self, id: u64, bundle: Bundle) {
+        self.dict.set(&id.to_string(), bundle);
+    }
+}
+
+const SIGNED_ORDER_NONCES_DICT: &str = "signed_order_nonces";
+
+pub struct SignedOrderNonces {
+    dict: Dict,
+}
+
+impl SignedOrderNonces {
+    pub fn instance() -> SignedOrderNonces {
+        SignedOrderNonces {
+            dict: Dict::instance(SIGNED_ORDER_NONCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SIGNED_ORDER_NONCES_DICT);
+    }
+
+    pub fn is_used(&self, creator: Address, nonce: u64) -> bool {
+        self.dict
+            .get(&key_and_value_to_str(&Key::from(creator), &nonce))
+            .unwrap_or_default()
+    }
+
+    pub fn set_used(&self, creator: Address, nonce: u64) {
+        self.dict
+            .set(&key_and_value_to_str(&Key::from(creator), &nonce), true);
+    }
+}
+
+const ORDER_EPOCHS_DICT: &str = "order_epochs";
+
+pub struct OrderEpochs {
+    dict: Dict,
+}
+
+impl OrderEpochs {
+    pub fn instance() -> OrderEpochs {
+        OrderEpochs {
+            dict: Dict::instance(ORDER_EPOCHS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ORDER_EPOCHS_DICT);
+    }
+
+    pub fn get(&self, creator: Address) -> u64 {
+        self.dict
+            .get_by_key(&Key::from(creator))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, creator: Address, epoch: u64) {
+        self.dict.set_by_key(&Key::from(creator), epoch);
+    }
+}
+
+const AUCTIONS_DICT: &str = "auctions";
+const CLOSED_AUCTIONS_DICT: &str = "closed_auctions";
+
+// Running auctions are keyed by token, settled and canceled ones are kept by id
+pub struct Auctions {
+    dict: Dict,
+    closed: Dict,
+}
+
+impl Auctions {
+    pub fn instance() -> Auctions {
+        Auctions {
+            dict: Dict::instance(AUCTIONS_DICT),
+            closed: Dict::instance(CLOSED_AUCTIONS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(AUCTIONS_DICT);
+        Dict::init(CLOSED_AUCTIONS_DICT);
+    }
+
+    // Moves the auction out of the running ones once its status is final
+    pub fn set_closed(&self, auction: Auction) {
+        self.remove(auction.collection, auction.token_id);
+        self.closed.set(&auction.id.to_string(), auction);
+    }
+
+    pub fn get(&self, contract_hash: ContractHash, token_id: TokenId) -> Auction {
+        self.dict
+            .get(&contract_hash_and_value_to_str(contract_hash, token_id))
+            .unwrap_or_revert_with(Error::NotExistAuction)
+    }
+
+    pub fn set(&self, contract_hash: ContractHash, token_id: TokenId, auction: Auction) {
+        self.dict.set(
+            &contract_hash_and_value_to_str(contract_hash, token_id),
+            auction,
+        );
+    }
+
+    pub fn remove(&self, contract_hash: ContractHash, token_id: TokenId) {
+        self.dict
+            .remove::<Auction>(&contract_hash_and_value_to_str(contract_hash, token_id));
+    }
+}
+
 const PURSE_KEY_NAME: &str = "deposit_purse";
 const PURSE_BALANCE_KEY_NAME: &str = "purse_balance";
 
@@ -131,20 +448,25 @@ impl DepositPurse {
 }
 
 const ACCEPTABLE_TOKENS_DICT: &str = "acceptable_tokens";
+const ACCEPTABLE_TOKEN_STANDARDS_DICT: &str = "acceptable_token_standards";
+const ACCEPTABLE_TOKEN_LIST_KEY: &str = "acceptable_token_list";
 
 pub struct AcceptableTokens {
     dict: Dict,
+    standards: Dict,
 }
 
 impl AcceptableTokens {
     pub fn instance() -> AcceptableTokens {
         AcceptableTokens {
             dict: Dict::instance(ACCEPTABLE_TOKENS_DICT),
+            standards: Dict::instance(ACCEPTABLE_TOKEN_STANDARDS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(ACCEPTABLE_TOKENS_DICT)
+        Dict::init(ACCEPTABLE_TOKENS_DICT);
+        Dict::init(ACCEPTABLE_TOKEN_STANDARDS_DICT)
     }
 
     pub fn get(&self, contract_hash: ContractHash) -> u32 {
@@ -153,13 +475,159 @@ impl AcceptableTokens {
             .unwrap_or_revert_with(Error::NotAcceptableToken)
     }
 
+    pub fn contains(&self, contract_hash: ContractHash) -> bool {
+        self.dict
+            .get::<u32>(&key_to_str(&Key::from(contract_hash)))
+            .is_some()
+    }
+
     pub fn set(&self, contract_hash: ContractHash, fee: u32) {
-        self.dict.set(&key_to_str(&Key::from(contract_hash)), fee)
+        self.dict.set(&key_to_str(&Key::from(contract_hash)), fee);
+        let mut list = self.list();
+        if !list.contains(&contract_hash) {
+            list.push(contract_hash);
+            set_key(ACCEPTABLE_TOKEN_LIST_KEY, list);
+        }
+    }
+
+    // Dictionaries can not be enumerated, so the acceptable tokens are also kept in a list
+    pub fn list(&self) -> Vec<ContractHash> {
+        get_key(ACCEPTABLE_TOKEN_LIST_KEY).unwrap_or_default()
+    }
+
+    pub fn get_standard(&self, contract_hash: ContractHash) -> FTStandard {
+        self.standards
+            .get::<u8>(&key_to_str(&Key::from(contract_hash)))
+            .map(|standard| FTStandard::from_u8(standard).unwrap_or_revert())
+            .unwrap_or_default()
+    }
+
+    pub fn set_standard(&self, contract_hash: ContractHash, standard: FTStandard) {
+        self.standards
+            .set(&key_to_str(&Key::from(contract_hash)), standard.as_u8())
     }
 
     pub fn remove(&self, contract_hash: ContractHash) {
         self.dict
-            .remove::<u32>(&key_to_str(&Key::from(contract_hash)))
+            .remove::<u32>(&key_to_str(&Key::from(contract_hash)));
+        self.standards
+            .remove::<u8>(&key_to_str(&Key::from(contract_hash)));
+        let mut list = self.list();
+        list.retain(|token| token.ne(&contract_hash));
+        set_key(ACCEPTABLE_TOKEN_LIST_KEY, list);
+    }
+}
+
+const ROYALTIES_DICT: &str = "royalties";
+
+pub struct Royalties {
+    dict: Dict,
+}
+
+impl Royalties {
+    pub fn instance() -> Royalties {
+        Royalties {
+            dict: Dict::instance(ROYALTIES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ROYALTIES_DICT)
+    }
+
+    pub fn get(&self, collection: ContractHash) -> Vec<Royalty> {
+        self.dict
+            .get(&key_to_str(&Key::from(collection)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, collection: ContractHash, shares: Vec<Royalty>) {
+        self.dict.set(&key_to_str(&Key::from(collection)), shares)
+    }
+}
+
+const ROYALTY_INFO_COLLECTIONS_DICT: &str = "royalty_info_collections";
+
+pub struct RoyaltyInfoCollections {
+    dict: Dict,
+}
+
+impl RoyaltyInfoCollections {
+    pub fn instance() -> RoyaltyInfoCollections {
+        RoyaltyInfoCollections {
+            dict: Dict::instance(ROYALTY_INFO_COLLECTIONS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ROYALTY_INFO_COLLECTIONS_DICT)
+    }
+
+    pub fn get(&self, collection: ContractHash) -> bool {
+        self.dict
+            .get(&key_to_str(&Key::from(collection)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, collection: ContractHash, supported: bool) {
+        self.dict
+            .set(&key_to_str(&Key::from(collection)), supported)
+    }
+}
+
+const COLLECTION_STANDARDS_DICT: &str = "collection_standards";
+
+pub struct CollectionStandards {
+    dict: Dict,
+}
+
+impl CollectionStandards {
+    pub fn instance() -> CollectionStandards {
+        CollectionStandards {
+            dict: Dict::instance(COLLECTION_STANDARDS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_STANDARDS_DICT)
+    }
+
+    pub fn get(&self, collection: ContractHash) -> NFTStandard {
+        self.dict
+            .get::<u8>(&key_to_str(&Key::from(collection)))
+            .map(|standard| NFTStandard::from_u8(standard).unwrap_or_revert())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, collection: ContractHash, standard: NFTStandard) {
+        self.dict
+            .set(&key_to_str(&Key::from(collection)), standard.as_u8())
+    }
+}
+
+const COLLECTION_ADMINS_DICT: &str = "collection_admins";
+
+pub struct CollectionAdmins {
+    dict: Dict,
+}
+
+impl CollectionAdmins {
+    pub fn instance() -> CollectionAdmins {
+        CollectionAdmins {
+            dict: Dict::instance(COLLECTION_ADMINS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(COLLECTION_ADMINS_DICT)
+    }
+
+    pub fn get(&self, collection: ContractHash) -> Option<Address> {
+        self.dict.get(&key_to_str(&Key::from(collection)))
+    }
+
+    pub fn set(&self, collection: ContractHash, admin: Address) {
+        self.dict.set(&key_to_str(&Key::from(collection)), admin)
     }
 }
 
@@ -173,29 +641,101 @@ pub fn get_fee_wallet() -> Address {
     get_key(FEE_WALLET_KEY).unwrap_or_revert()
 }
 
+const LAST_ORDER_ID_KEY: &str = "last_order_id";
+
+pub fn next_order_id() -> u64 {
+    let id = get_key::<u64>(LAST_ORDER_ID_KEY).unwrap_or_default() + 1;
+    set_key(LAST_ORDER_ID_KEY, id);
+    id
+}
+
+const MAX_AUCTION_EXTENSION_KEY: &str = "max_auction_extension";
+// cap on `extension_window` until an admin sets one, ten minutes of block time
+const DEFAULT_MAX_AUCTION_EXTENSION: Time = 600;
+
+pub fn set_max_auction_extension(window: Time) {
+    set_key(MAX_AUCTION_EXTENSION_KEY, window);
+}
+
+pub fn get_max_auction_extension() -> Time {
+    get_key(MAX_AUCTION_EXTENSION_KEY).unwrap_or(DEFAULT_MAX_AUCTION_EXTENSION)
+}
+
+// `collection:token_id` pairs separated by commas
+fn items_to_str(items: &[Token]) -> String {
+    items
+        .iter()
+        .map(|(collection, token_id)| format!("{}:{}", collection, token_id))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
 pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash) {
     let mut events = Vec::new();
     match event {
         MarketplaceEvent::SellOrderCreated {
+            id,
             creator,
             collection,
             token_id,
             pay_token,
             price,
             start_time,
+            end_time,
+            dutch_auction,
+            allowlist_root,
+            reserved_buyer,
+            custodial,
         } => {
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "SellOrderCreated".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
             param.insert("pay_token", format!("{:?}", pay_token));
             param.insert("price", format!("{}", price));
             param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{:?}", end_time));
+            if let Some(dutch_auction) = dutch_auction {
+                param.insert("end_price", format!("{}", dutch_auction.end_price));
+                param.insert("duration", format!("{}", dutch_auction.duration));
+            }
+            if let Some(allowlist_root) = allowlist_root {
+                param.insert("allowlist_root", hex::encode(allowlist_root));
+            }
+            if let Some(reserved_buyer) = reserved_buyer {
+                param.insert("reserved_buyer", format!("{:?}", reserved_buyer));
+            }
+            param.insert("custodial", format!("{}", custodial));
+            events.push(param);
+        }
+        MarketplaceEvent::SellOrderUpdated {
+            id,
+            creator,
+            collection,
+            token_id,
+            pay_token,
+            price,
+            start_time,
+            end_time,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "SellOrderUpdated".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("pay_token", format!("{:?}", pay_token));
+            param.insert("price", format!("{}", price));
+            param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{:?}", end_time));
             events.push(param);
         }
         MarketplaceEvent::SellOrderCanceled {
+            id,
             creator,
             collection,
             token_id,
@@ -204,6 +744,24 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "SellOrderCanceled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("start_time", format!("{}", start_time));
+            events.push(param);
+        }
+        MarketplaceEvent::SellOrderInvalidated {
+            id,
+            creator,
+            collection,
+            token_id,
+            start_time,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "SellOrderInvalidated".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
@@ -211,16 +769,20 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
             events.push(param);
         }
         MarketplaceEvent::SellOrderBought {
+            id,
             creator,
             collection,
             token_id,
             buyer,
             additional_recipient,
             start_time,
+            price,
+            royalty,
         } => {
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "SellOrderBought".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
@@ -230,9 +792,106 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
                 format!("{:?}", additional_recipient),
             );
             param.insert("start_time", format!("{}", start_time));
+            param.insert("price", format!("{}", price));
+            param.insert("royalty", format!("{}", royalty));
+            events.push(param);
+        }
+        MarketplaceEvent::BundleSellOrderCreated {
+            id,
+            creator,
+            items,
+            pay_token,
+            price,
+            start_time,
+            end_time,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "BundleSellOrderCreated".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("items", items_to_str(items));
+            param.insert("pay_token", format!("{:?}", pay_token));
+            param.insert("price", format!("{}", price));
+            param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{:?}", end_time));
+            events.push(param);
+        }
+        MarketplaceEvent::BundleSellOrderCanceled { id, creator } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "BundleSellOrderCanceled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            events.push(param);
+        }
+        MarketplaceEvent::BundleSellOrderBought {
+            id,
+            creator,
+            buyer,
+            price,
+            royalty,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "BundleSellOrderBought".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("buyer", format!("{:?}", buyer));
+            param.insert("price", format!("{}", price));
+            param.insert("royalty", format!("{}", royalty));
+            events.push(param);
+        }
+        MarketplaceEvent::SignedOrderFulfilled {
+            id,
+            creator,
+            collection,
+            token_id,
+            buyer,
+            pay_token,
+            price,
+            nonce,
+            royalty,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "SignedOrderFulfilled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("buyer", format!("{:?}", buyer));
+            param.insert("pay_token", format!("{:?}", pay_token));
+            param.insert("price", format!("{}", price));
+            param.insert("nonce", format!("{}", nonce));
+            param.insert("royalty", format!("{}", royalty));
+            events.push(param);
+        }
+        MarketplaceEvent::OrderEpochIncremented { creator, epoch } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "OrderEpochIncremented".to_string());
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("epoch", format!("{}", epoch));
+            events.push(param);
+        }
+        MarketplaceEvent::SignedOrdersCanceled { creator, nonces } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "SignedOrdersCanceled".to_string());
+            param.insert("creator", format!("{:?}", creator));
+            param.insert(
+                "nonces",
+                nonces
+                    .iter()
+                    .map(|nonce| nonce.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
             events.push(param);
         }
         MarketplaceEvent::BuyOrderCreated {
+            id,
             creator,
             collection,
             token_id,
@@ -240,10 +899,12 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
             price,
             additional_recipient,
             start_time,
+            end_time,
         } => {
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "BuyOrderCreated".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
@@ -254,9 +915,28 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
                 format!("{:?}", additional_recipient),
             );
             param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{:?}", end_time));
+            events.push(param);
+        }
+        MarketplaceEvent::BuyOrderUpdated {
+            id,
+            creator,
+            collection,
+            token_id,
+            price,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "BuyOrderUpdated".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("price", format!("{}", price));
             events.push(param);
         }
         MarketplaceEvent::BuyOrderCanceled {
+            id,
             creator,
             collection,
             token_id,
@@ -265,6 +945,7 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "BuyOrderCanceled".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
@@ -273,23 +954,195 @@ pub fn emit(event: &MarketplaceEvent, contract_package_hash: ContractPackageHash
             events.push(param);
         }
         MarketplaceEvent::BuyOrderAccepted {
+            id,
             creator,
             collection,
             token_id,
             start_time,
             owner,
+            royalty,
         } => {
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
             param.insert("event_type", "BuyOrderAccepted".to_string());
+            param.insert("id", format!("{}", id));
             param.insert("creator", format!("{:?}", creator));
             param.insert("collection", collection.to_string());
             param.insert("token_id", format!("{}", token_id));
             param.insert("start_time", format!("{}", start_time));
             param.insert("owner", format!("{:?}", owner));
+            param.insert("royalty", format!("{}", royalty));
 
             events.push(param);
         }
+        MarketplaceEvent::CollectionOfferCreated {
+            id,
+            creator,
+            collection,
+            pay_token,
+            price,
+            quantity,
+            start_time,
+            end_time,
+            merkle_root,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "CollectionOfferCreated".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("pay_token", format!("{:?}", pay_token));
+            param.insert("price", format!("{}", price));
+            param.insert("quantity", format!("{}", quantity));
+            param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{:?}", end_time));
+            if let Some(merkle_root) = merkle_root {
+                param.insert("merkle_root", hex::encode(merkle_root));
+            }
+            events.push(param);
+        }
+        MarketplaceEvent::CollectionOfferCanceled {
+            id,
+            creator,
+            collection,
+            quantity,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "CollectionOfferCanceled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("quantity", format!("{}", quantity));
+            events.push(param);
+        }
+        MarketplaceEvent::CollectionOfferAccepted {
+            id,
+            creator,
+            collection,
+            token_id,
+            owner,
+            price,
+            remaining,
+            royalty,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "CollectionOfferAccepted".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("owner", format!("{:?}", owner));
+            param.insert("price", format!("{}", price));
+            param.insert("remaining", format!("{}", remaining));
+            param.insert("royalty", format!("{}", royalty));
+            events.push(param);
+        }
+        MarketplaceEvent::AuctionCreated {
+            id,
+            creator,
+            collection,
+            token_id,
+            pay_token,
+            reserve_price,
+            min_bid_increment,
+            start_time,
+            end_time,
+            extension_window,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "AuctionCreated".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("pay_token", format!("{:?}", pay_token));
+            param.insert("reserve_price", format!("{}", reserve_price));
+            param.insert("min_bid_increment", format!("{}", min_bid_increment));
+            param.insert("start_time", format!("{}", start_time));
+            param.insert("end_time", format!("{}", end_time));
+            param.insert("extension_window", format!("{}", extension_window));
+            events.push(param);
+        }
+        MarketplaceEvent::AuctionBid {
+            id,
+            collection,
+            token_id,
+            bidder,
+            amount,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "AuctionBid".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("bidder", format!("{:?}", bidder));
+            param.insert("amount", format!("{}", amount));
+            events.push(param);
+        }
+        MarketplaceEvent::AuctionExtended {
+            id,
+            collection,
+            token_id,
+            end_time,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "AuctionExtended".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("end_time", format!("{}", end_time));
+            events.push(param);
+        }
+        MarketplaceEvent::AuctionSettled {
+            id,
+            creator,
+            collection,
+            token_id,
+            winner,
+            amount,
+            royalty,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "AuctionSettled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            param.insert("winner", format!("{:?}", winner));
+            param.insert("amount", format!("{}", amount));
+            param.insert("royalty", format!("{}", royalty));
+            events.push(param);
+        }
+        MarketplaceEvent::AuctionCanceled {
+            id,
+            creator,
+            collection,
+            token_id,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "AuctionCanceled".to_string());
+            param.insert("id", format!("{}", id));
+            param.insert("creator", format!("{:?}", creator));
+            param.insert("collection", collection.to_string());
+            param.insert("token_id", format!("{}", token_id));
+            events.push(param);
+        }
+        MarketplaceEvent::RoyaltyUpdated { collection, shares } => {
+            let mut param = BTreeMap::new();
+            param.insert("contract_package_hash", contract_package_hash.to_string());
+            param.insert("event_type", "RoyaltyUpdated".to_string());
+            param.insert("collection", collection.to_string());
+            param.insert("shares", format!("{:?}", shares));
+            events.push(param);
+        }
         MarketplaceEvent::AcceptableTokenAdded { contract_hash, fee } => {
             let mut param = BTreeMap::new();
             param.insert("contract_package_hash", contract_package_hash.to_string());
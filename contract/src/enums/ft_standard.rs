@@ -0,0 +1,37 @@
+//! Implementation of an `FTStandard` which refers to the fungible token standard a pay token implements.
+
+use crate::Error;
+
+/// Fungible token standards a pay token can be registered with.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FTStandard {
+    /// Legacy Casper ERC-20 contract.
+    ERC20,
+    /// CEP-18 contract with `Key` based owners.
+    CEP18,
+}
+
+impl FTStandard {
+    /// Returns the `u8` representation stored on-chain.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            FTStandard::ERC20 => 0,
+            FTStandard::CEP18 => 1,
+        }
+    }
+
+    /// Parses the `u8` representation stored on-chain.
+    pub fn from_u8(value: u8) -> Result<FTStandard, Error> {
+        match value {
+            0 => Ok(FTStandard::ERC20),
+            1 => Ok(FTStandard::CEP18),
+            _ => Err(Error::InvalidFTStandard),
+        }
+    }
+}
+
+impl Default for FTStandard {
+    fn default() -> Self {
+        FTStandard::ERC20
+    }
+}
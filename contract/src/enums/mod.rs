@@ -1,3 +1,9 @@
 mod address;
+mod ft_standard;
+mod nft_standard;
+mod order_status;
 
 pub use address::Address;
+pub use ft_standard::FTStandard;
+pub use nft_standard::NFTStandard;
+pub use order_status::OrderStatus;
@@ -0,0 +1,41 @@
+//! Implementation of an `NFTStandard` which refers to the token standard a collection implements.
+
+use crate::Error;
+
+/// NFT standards a collection can be registered with.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum NFTStandard {
+    /// CEP-47 collection with `U256` token ids.
+    CEP47,
+    /// CEP-78 collection in the `Ordinal` identifier mode, token ids are `u64`.
+    CEP78Ordinal,
+    /// CEP-78 collection in the `Hash` identifier mode, token hashes are hex encoded 32 bytes.
+    CEP78Hash,
+}
+
+impl NFTStandard {
+    /// Returns the `u8` representation stored on-chain.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            NFTStandard::CEP47 => 0,
+            NFTStandard::CEP78Ordinal => 1,
+            NFTStandard::CEP78Hash => 2,
+        }
+    }
+
+    /// Parses the `u8` representation stored on-chain.
+    pub fn from_u8(value: u8) -> Result<NFTStandard, Error> {
+        match value {
+            0 => Ok(NFTStandard::CEP47),
+            1 => Ok(NFTStandard::CEP78Ordinal),
+            2 => Ok(NFTStandard::CEP78Hash),
+            _ => Err(Error::InvalidNFTStandard),
+        }
+    }
+}
+
+impl Default for NFTStandard {
+    fn default() -> Self {
+        NFTStandard::CEP47
+    }
+}
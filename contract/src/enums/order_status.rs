@@ -0,0 +1,74 @@
+//! Implementation of an `OrderStatus` which refers to the lifecycle state of an order.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+use crate::Error;
+
+/// Lifecycle states of orders, bundles, auctions and collection offers, closed ones are retained
+/// for auditing.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum OrderStatus {
+    /// Open and fillable once started.
+    Pending,
+    /// Filled by a buyer, accepted by the token owner or settled after an auction.
+    Completed,
+    /// Withdrawn by its creator.
+    Canceled,
+    /// Closed after its end time passed.
+    Expired,
+    /// Closed because the token or funds backing it are gone.
+    Invalidated,
+}
+
+impl OrderStatus {
+    /// Returns the `u8` representation stored on-chain.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            OrderStatus::Pending => 0,
+            OrderStatus::Completed => 1,
+            OrderStatus::Canceled => 2,
+            OrderStatus::Expired => 3,
+            OrderStatus::Invalidated => 4,
+        }
+    }
+
+    /// Parses the `u8` representation stored on-chain.
+    pub fn from_u8(value: u8) -> Result<OrderStatus, Error> {
+        match value {
+            0 => Ok(OrderStatus::Pending),
+            1 => Ok(OrderStatus::Completed),
+            2 => Ok(OrderStatus::Canceled),
+            3 => Ok(OrderStatus::Expired),
+            4 => Ok(OrderStatus::Invalidated),
+            _ => Err(Error::InvalidOrderStatus),
+        }
+    }
+}
+
+impl CLTyped for OrderStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for OrderStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.as_u8().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.as_u8().serialized_length()
+    }
+}
+
+impl FromBytes for OrderStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (value, remainder) = u8::from_bytes(bytes)?;
+        let status = OrderStatus::from_u8(value).map_err(|_| bytesrepr::Error::Formatting)?;
+        Ok((status, remainder))
+    }
+}
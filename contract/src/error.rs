@@ -27,6 +27,34 @@ pub enum Error {
     /// Operation would cause an integer overflow.
     Overflow,
     InvalidContext,
+    NotStartedOrder,
+    ExpiredOrder,
+    NotExpiredOrder,
+    InvalidOrderTime,
+    NotExistAuction,
+    NotStartedAuction,
+    FinishedAuction,
+    NotFinishedAuction,
+    InsufficientBid,
+    AlreadyBidAuction,
+    InvalidBidIncrement,
+    InvalidExtensionWindow,
+    InvalidDutchAuction,
+    InvalidRoyalty,
+    InvalidNFTStandard,
+    InvalidQuantity,
+    InvalidMerkleRoot,
+    NotReservedBuyer,
+    InvalidBundle,
+    UnavailableOrder,
+    StaleOrder,
+    InvalidSignature,
+    UsedNonce,
+    InvalidEpoch,
+    InvalidPrice,
+    InvalidOrderStatus,
+    InvalidFTStandard,
+    InvalidProof,
 }
 
 impl From<Error> for ApiError {
@@ -1,31 +1,102 @@
+use alloc::{string::String, vec::Vec};
 use casper_types::{ContractHash, U256};
 
-use crate::{Address, Time, TokenId};
+use crate::{
+    structs::{order::DutchAuction, royalty::Royalty},
+    Address, Time, Token, TokenId,
+};
 
 pub enum MarketplaceEvent {
     SellOrderCreated {
+        id: u64,
         creator: Address,
         collection: ContractHash,
         token_id: TokenId,
         pay_token: Option<ContractHash>,
         price: U256,
         start_time: Time,
+        end_time: Option<Time>,
+        dutch_auction: Option<DutchAuction>,
+        allowlist_root: Option<[u8; 32]>,
+        reserved_buyer: Option<Address>,
+        custodial: bool,
+    },
+    SellOrderUpdated {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        start_time: Time,
+        end_time: Option<Time>,
     },
     SellOrderCanceled {
+        id: u64,
+        creator: Address,
+        start_time: Time,
+        collection: ContractHash,
+        token_id: TokenId,
+    },
+    SellOrderInvalidated {
+        id: u64,
         creator: Address,
         start_time: Time,
         collection: ContractHash,
         token_id: TokenId,
     },
     SellOrderBought {
+        id: u64,
         creator: Address,
         start_time: Time,
         collection: ContractHash,
         token_id: TokenId,
         buyer: Address,
         additional_recipient: Option<Address>,
+        price: U256,
+        royalty: U256,
+    },
+    BundleSellOrderCreated {
+        id: u64,
+        creator: Address,
+        items: Vec<Token>,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        start_time: Time,
+        end_time: Option<Time>,
+    },
+    BundleSellOrderCanceled {
+        id: u64,
+        creator: Address,
+    },
+    BundleSellOrderBought {
+        id: u64,
+        creator: Address,
+        buyer: Address,
+        price: U256,
+        royalty: U256,
+    },
+    SignedOrderFulfilled {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        buyer: Address,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        nonce: u64,
+        royalty: U256,
+    },
+    OrderEpochIncremented {
+        creator: Address,
+        epoch: u64,
+    },
+    SignedOrdersCanceled {
+        creator: Address,
+        nonces: Vec<u64>,
     },
     BuyOrderCreated {
+        id: u64,
         creator: Address,
         collection: ContractHash,
         token_id: TokenId,
@@ -33,19 +104,101 @@ pub enum MarketplaceEvent {
         price: U256,
         additional_recipient: Option<Address>,
         start_time: Time,
+        end_time: Option<Time>,
+    },
+    BuyOrderUpdated {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        price: U256,
     },
     BuyOrderCanceled {
+        id: u64,
         creator: Address,
         collection: ContractHash,
         token_id: TokenId,
         start_time: Time,
     },
     BuyOrderAccepted {
+        id: u64,
         creator: Address,
         collection: ContractHash,
         token_id: TokenId,
         start_time: Time,
         owner: Address,
+        royalty: U256,
+    },
+    CollectionOfferCreated {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        quantity: u32,
+        start_time: Time,
+        end_time: Option<Time>,
+        merkle_root: Option<[u8; 32]>,
+    },
+    CollectionOfferCanceled {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        quantity: u32,
+    },
+    CollectionOfferAccepted {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        owner: Address,
+        price: U256,
+        remaining: u32,
+        royalty: U256,
+    },
+    AuctionCreated {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        pay_token: Option<ContractHash>,
+        reserve_price: U256,
+        min_bid_increment: U256,
+        start_time: Time,
+        end_time: Time,
+        extension_window: Time,
+    },
+    AuctionBid {
+        id: u64,
+        collection: ContractHash,
+        token_id: TokenId,
+        bidder: Address,
+        amount: U256,
+    },
+    AuctionExtended {
+        id: u64,
+        collection: ContractHash,
+        token_id: TokenId,
+        end_time: Time,
+    },
+    AuctionSettled {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        winner: Option<Address>,
+        amount: U256,
+        royalty: U256,
+    },
+    AuctionCanceled {
+        id: u64,
+        creator: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+    },
+    RoyaltyUpdated {
+        collection: ContractHash,
+        shares: Vec<Royalty>,
     },
     AcceptableTokenAdded {
         contract_hash: ContractHash,
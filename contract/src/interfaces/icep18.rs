@@ -0,0 +1,89 @@
+#![allow(dead_code)]
+use alloc::string::String;
+use casper_contract::contract_api::runtime;
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, U256};
+
+use crate::Address;
+
+// CEP-18 takes owners, spenders and recipients as `Key`s. An allowance is consumed by
+// `transfer_from` and replaced, not increased, by `approve`.
+pub struct ICEP18 {
+    pub contract_hash: ContractHash,
+}
+
+impl ICEP18 {
+    pub fn new(contract_hash: ContractHash) -> Self {
+        ICEP18 { contract_hash }
+    }
+
+    pub fn name(&self) -> String {
+        runtime::call_contract(self.contract_hash, "name", runtime_args! {})
+    }
+
+    pub fn symbol(&self) -> String {
+        runtime::call_contract(self.contract_hash, "symbol", runtime_args! {})
+    }
+
+    pub fn transfer_from(&self, owner: Address, recipient: Address, amount: U256) {
+        runtime::call_contract(
+            self.contract_hash,
+            "transfer_from",
+            runtime_args! {
+              "owner" => Key::from(owner),
+              "recipient" => Key::from(recipient),
+              "amount" => amount,
+            },
+        )
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        runtime::call_contract(
+            self.contract_hash,
+            "allowance",
+            runtime_args! {
+              "owner" => Key::from(owner),
+              "spender" => Key::from(spender),
+            },
+        )
+    }
+
+    pub fn approve(&self, spender: Address, amount: U256) {
+        runtime::call_contract(
+            self.contract_hash,
+            "approve",
+            runtime_args! {
+              "spender" => Key::from(spender),
+              "amount" => amount,
+            },
+        )
+    }
+
+    pub fn transfer(&self, recipient: Address, amount: U256) {
+        runtime::call_contract(
+            self.contract_hash,
+            "transfer",
+            runtime_args! {
+              "recipient" => Key::from(recipient),
+              "amount" => amount,
+            },
+        )
+    }
+
+    pub fn balance_of(&self, address: Address) -> U256 {
+        runtime::call_contract(
+            self.contract_hash,
+            "balance_of",
+            runtime_args! {
+              "address" => Key::from(address),
+            },
+        )
+    }
+
+    pub fn total_supply(&self) -> U256 {
+        runtime::call_contract(self.contract_hash, "total_supply", runtime_args! {})
+    }
+
+    pub fn decimals(&self) -> u8 {
+        runtime::call_contract(self.contract_hash, "decimals", runtime_args! {})
+    }
+}
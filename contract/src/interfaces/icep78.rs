@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+use alloc::string::String;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs};
+
+use crate::{Address, Error, TokenId};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenIdentifierMode {
+    Ordinal,
+    Hash,
+}
+
+pub struct ICEP78 {
+    pub contract_hash: ContractHash,
+    pub identifier_mode: TokenIdentifierMode,
+}
+
+impl ICEP78 {
+    pub fn new(contract_hash: ContractHash, identifier_mode: TokenIdentifierMode) -> Self {
+        ICEP78 {
+            contract_hash,
+            identifier_mode,
+        }
+    }
+
+    pub fn approve(&self, operator: Address, token_id: TokenId) {
+        let mut args = runtime_args! {
+          "operator" => Key::from(operator),
+        };
+        self.insert_token_identifier(&mut args, token_id);
+        runtime::call_contract::<()>(self.contract_hash, "approve", args);
+    }
+
+    pub fn get_approved(&self, token_id: TokenId) -> Option<Address> {
+        let mut args = RuntimeArgs::new();
+        self.insert_token_identifier(&mut args, token_id);
+        runtime::call_contract(self.contract_hash, "get_approved", args)
+    }
+
+    pub fn owner_of(&self, token_id: TokenId) -> Address {
+        let mut args = RuntimeArgs::new();
+        self.insert_token_identifier(&mut args, token_id);
+        runtime::call_contract(self.contract_hash, "owner_of", args)
+    }
+
+    // The caller must be the owner of the token or its approved operator.
+    pub fn transfer(&self, source: Address, target: Address, token_id: TokenId) {
+        let mut args = runtime_args! {
+          "source_key" => Key::from(source),
+          "target_key" => Key::from(target),
+        };
+        self.insert_token_identifier(&mut args, token_id);
+        runtime::call_contract::<(String, Key)>(self.contract_hash, "transfer", args);
+    }
+
+    fn insert_token_identifier(&self, args: &mut RuntimeArgs, token_id: TokenId) {
+        match self
+            .identifier_mode
+            .token_identifier(token_id)
+            .unwrap_or_revert()
+        {
+            TokenIdentifier::Index(index) => args.insert("token_id", index),
+            TokenIdentifier::Hash(hash) => args.insert("token_hash", hash),
+        }
+        .unwrap_or_revert();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenIdentifier {
+    Index(u64),
+    Hash(String),
+}
+
+impl TokenIdentifierMode {
+    // `Ordinal` ids are passed as `token_id: u64` and `Hash` ids as `token_hash: String`, the hex
+    // encoding of the 32 byte big endian `TokenId`.
+    pub fn token_identifier(&self, token_id: TokenId) -> Result<TokenIdentifier, Error> {
+        match self {
+            TokenIdentifierMode::Ordinal => {
+                if token_id.gt(&TokenId::from(u64::MAX)) {
+                    return Err(Error::NotExistToken);
+                }
+                Ok(TokenIdentifier::Index(token_id.as_u64()))
+            }
+            TokenIdentifierMode::Hash => {
+                let mut bytes = [0u8; 32];
+                token_id.to_big_endian(&mut bytes);
+                Ok(TokenIdentifier::Hash(hex::encode(bytes)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::{TokenIdentifier, TokenIdentifierMode};
+    use crate::{Error, TokenId};
+
+    #[test]
+    fn should_pass_ordinal_ids_as_u64() {
+        let mode = TokenIdentifierMode::Ordinal;
+        assert_eq!(
+            mode.token_identifier(TokenId::from(u64::MAX)).ok(),
+            Some(TokenIdentifier::Index(u64::MAX))
+        );
+        assert!(matches!(
+            mode.token_identifier(TokenId::from(u64::MAX) + 1),
+            Err(Error::NotExistToken)
+        ));
+    }
+
+    #[test]
+    fn should_pass_hash_ids_as_big_endian_hex() {
+        let mode = TokenIdentifierMode::Hash;
+        assert_eq!(
+            mode.token_identifier(TokenId::from(0x0102u64)).ok(),
+            Some(TokenIdentifier::Hash(
+                "0000000000000000000000000000000000000000000000000000000000000102".to_string()
+            ))
+        );
+        assert_eq!(
+            mode.token_identifier(TokenId::max_value()).ok(),
+            Some(TokenIdentifier::Hash("f".repeat(64)))
+        );
+    }
+}
@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+use casper_types::{ContractHash, U256};
+
+use crate::{
+    enums::FTStandard,
+    interfaces::{icep18::ICEP18, ierc20::IERC20},
+    Address,
+};
+
+/// Dispatches pay token calls to the interface of the standard the token is registered with.
+pub struct IFT {
+    pub contract_hash: ContractHash,
+    pub standard: FTStandard,
+}
+
+impl IFT {
+    pub fn new(contract_hash: ContractHash, standard: FTStandard) -> Self {
+        IFT {
+            contract_hash,
+            standard,
+        }
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        match self.standard {
+            FTStandard::ERC20 => IERC20::new(self.contract_hash).allowance(owner, spender),
+            FTStandard::CEP18 => ICEP18::new(self.contract_hash).allowance(owner, spender),
+        }
+    }
+
+    pub fn balance_of(&self, address: Address) -> U256 {
+        match self.standard {
+            FTStandard::ERC20 => IERC20::new(self.contract_hash).balance_of(address),
+            FTStandard::CEP18 => ICEP18::new(self.contract_hash).balance_of(address),
+        }
+    }
+
+    pub fn transfer(&self, recipient: Address, amount: U256) {
+        match self.standard {
+            FTStandard::ERC20 => IERC20::new(self.contract_hash).transfer(recipient, amount),
+            FTStandard::CEP18 => ICEP18::new(self.contract_hash).transfer(recipient, amount),
+        }
+    }
+
+    pub fn transfer_from(&self, owner: Address, recipient: Address, amount: U256) {
+        match self.standard {
+            FTStandard::ERC20 => {
+                IERC20::new(self.contract_hash).transfer_from(owner, recipient, amount)
+            }
+            FTStandard::CEP18 => {
+                ICEP18::new(self.contract_hash).transfer_from(owner, recipient, amount)
+            }
+        }
+    }
+}
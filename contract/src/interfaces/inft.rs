@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+use alloc::vec;
+use casper_types::ContractHash;
+
+use crate::{
+    enums::NFTStandard,
+    interfaces::{
+        icep47::ICEP47,
+        icep78::{TokenIdentifierMode, ICEP78},
+    },
+    Address, TokenId,
+};
+
+/// Dispatches NFT calls to the interface of the standard a collection is registered with.
+pub struct INFT {
+    pub contract_hash: ContractHash,
+    pub standard: NFTStandard,
+    /// Address of the marketplace, used as the source of transfers out of escrow.
+    pub marketplace: Address,
+}
+
+impl INFT {
+    pub fn new(contract_hash: ContractHash, standard: NFTStandard, marketplace: Address) -> Self {
+        INFT {
+            contract_hash,
+            standard,
+            marketplace,
+        }
+    }
+
+    pub fn get_approved(&self, owner: Address, token_id: TokenId) -> Option<Address> {
+        match self.standard {
+            NFTStandard::CEP47 => ICEP47::new(self.contract_hash).get_approved(owner, token_id),
+            NFTStandard::CEP78Ordinal | NFTStandard::CEP78Hash => {
+                self.cep78().get_approved(token_id)
+            }
+        }
+    }
+
+    pub fn owner_of(&self, token_id: TokenId) -> Option<Address> {
+        match self.standard {
+            NFTStandard::CEP47 => ICEP47::new(self.contract_hash).owner_of(token_id),
+            NFTStandard::CEP78Ordinal | NFTStandard::CEP78Hash => {
+                Some(self.cep78().owner_of(token_id))
+            }
+        }
+    }
+
+    /// Transfers a token held by the marketplace to `recipient`.
+    pub fn transfer(&self, recipient: Address, token_id: TokenId) {
+        match self.standard {
+            NFTStandard::CEP47 => {
+                ICEP47::new(self.contract_hash).transfer(recipient, vec![token_id])
+            }
+            NFTStandard::CEP78Ordinal | NFTStandard::CEP78Hash => {
+                self.cep78().transfer(self.marketplace, recipient, token_id)
+            }
+        }
+    }
+
+    /// Transfers a token the marketplace is approved for from `sender` to `recipient`.
+    pub fn transfer_from(&self, sender: Address, recipient: Address, token_id: TokenId) {
+        match self.standard {
+            NFTStandard::CEP47 => {
+                ICEP47::new(self.contract_hash).transfer_from(sender, recipient, vec![token_id])
+            }
+            NFTStandard::CEP78Ordinal | NFTStandard::CEP78Hash => {
+                self.cep78().transfer(sender, recipient, token_id)
+            }
+        }
+    }
+
+    fn cep78(&self) -> ICEP78 {
+        let identifier_mode = match self.standard {
+            NFTStandard::CEP78Hash => TokenIdentifierMode::Hash,
+            _ => TokenIdentifierMode::Ordinal,
+        };
+        ICEP78::new(self.contract_hash, identifier_mode)
+    }
+}
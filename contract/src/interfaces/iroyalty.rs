@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+use casper_contract::contract_api::runtime;
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, U256};
+
+use crate::{Address, TokenId};
+
+pub struct IRoyalty {
+    pub contract_hash: ContractHash,
+}
+
+impl IRoyalty {
+    pub fn new(contract_hash: ContractHash) -> Self {
+        IRoyalty { contract_hash }
+    }
+
+    /// Returns the royalty recipient and the royalty amount owed on `sale_price`.
+    pub fn royalty_info(&self, token_id: TokenId, sale_price: U256) -> (Address, U256) {
+        runtime::call_contract(
+            self.contract_hash,
+            "royalty_info",
+            runtime_args! {
+              "token_id" => token_id,
+              "sale_price" => sale_price
+            },
+        )
+    }
+}
@@ -1,3 +1,8 @@
 
+pub mod icep18;
 pub mod icep47;
+pub mod icep78;
 pub mod ierc20;
+pub mod ift;
+pub mod inft;
+pub mod iroyalty;
@@ -16,8 +16,12 @@ pub type Time = u64;
 pub type TokenId = U256;
 pub type Token = (ContractHash, TokenId);
 pub type Bids = BTreeMap<Address, BuyOrder>;
-pub use enums::Address;
+pub use enums::{Address, FTStandard, NFTStandard, OrderStatus};
 pub use libs::address_utils::get_immediate_caller_address;
 pub use marketplace::Marketplace;
-use structs::order::BuyOrder;
-pub use structs::order::SellOrder;
+pub use structs::{
+    auction::Auction,
+    bundle::Bundle,
+    order::{BuyOrder, CollectionOffer, SellOrder, SignedOrder},
+    royalty::Royalty,
+};
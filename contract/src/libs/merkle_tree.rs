@@ -1,15 +1,16 @@
-#![allow(dead_code)]
 //! Implementation of merkle_tree.
 
 use alloc::string::String;
 
 use alloc::vec::Vec;
 
-use casper_contract::contract_api::runtime;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 
 use casper_types::ApiError;
 use tiny_keccak::Hasher;
 
+use crate::Error;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Position {
     Left = 0,
@@ -17,23 +18,25 @@ enum Position {
 }
 
 impl Position {
-    pub fn from_u8(value: u8) -> Position {
+    pub fn from_u8(value: u8) -> Result<Position, Error> {
         match value {
-            0 => Position::Left,
-            1 => Position::Right,
-            _ => panic!("Unknown value: {}", value),
+            0 => Ok(Position::Left),
+            1 => Ok(Position::Right),
+            _ => Err(Error::InvalidProof),
         }
     }
 }
 
-/// Verify leaf is in the tree
+/// Verify leaf is in the tree, reverts with `InvalidProof` when the proof is malformed
 pub fn verify(root: String, leaf: String, proof: Vec<(String, u8)>) {
     let converted_proof: Vec<(Vec<u8>, Position)> = proof
         .iter()
         .map(|proof| {
             (
-                hex::decode(proof.0.clone()).unwrap(),
-                Position::from_u8(proof.1),
+                hex::decode(&proof.0)
+                    .map_err(|_| Error::InvalidProof)
+                    .unwrap_or_revert(),
+                Position::from_u8(proof.1).unwrap_or_revert(),
             )
         })
         .collect();
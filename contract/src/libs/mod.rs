@@ -1,5 +1,5 @@
 pub mod address_utils;
 mod conversion;
 mod cspr_transfer;
-mod merkle_tree;
+pub mod merkle_tree;
 pub use conversion::{u256_to_512, u512_to_u256};
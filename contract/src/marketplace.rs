@@ -1,26 +1,53 @@
-use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use casper_contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    bytesrepr::ToBytes, runtime_args, CLTyped, ContractHash, ContractPackageHash, RuntimeArgs,
-    URef, U256, U512,
+    bytesrepr::ToBytes, crypto, runtime_args, CLTyped, ContractHash, ContractPackageHash, Key,
+    PublicKey, RuntimeArgs, Signature, URef, U256, U512,
 };
 use contract_utils::{set_key, ContractContext, ContractStorage};
 
 use crate::{
-    data::{self, AcceptableTokens, BuyOrders, DepositPurse, SellOrders},
+    data::{
+        self, AcceptableTokens, Auctions, Bundles, BuyOrders, CollectionAdmins, CollectionOffers,
+        CollectionStandards, DepositPurse, OrderEpochs, OrderIndex, Royalties,
+        RoyaltyInfoCollections, SellOrders, SignedOrderNonces,
+    },
+    enums::{FTStandard, NFTStandard, OrderStatus},
     event::MarketplaceEvent,
-    interfaces::{icep47::ICEP47, ierc20::IERC20},
-    libs::{u256_to_512, u512_to_u256},
-    structs::order::{BuyOrder, SellOrder},
-    Address, Error, Time, TokenId,
+    interfaces::{ift::IFT, inft::INFT, iroyalty::IRoyalty},
+    libs::{merkle_tree, u256_to_512, u512_to_u256},
+    structs::{
+        auction::Auction,
+        bundle::Bundle,
+        order::{BuyOrder, CollectionOffer, DutchAuction, SellOrder, SignedOrder},
+        royalty::Royalty,
+    },
+    Address, Bids, Error, Time, Token, TokenId,
 };
+const MAX_ROYALTY_BPS: u32 = 2500;
+
 pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
     fn init(&mut self, acceptable_tokens: BTreeMap<String, u32>, fee_wallet: Address) {
         SellOrders::init();
         BuyOrders::init();
+        CollectionOffers::init();
+        Auctions::init();
+        Bundles::init();
+        SignedOrderNonces::init();
+        data::init_order_indexes();
+        OrderEpochs::init();
+        Royalties::init();
+        RoyaltyInfoCollections::init();
+        CollectionAdmins::init();
+        CollectionStandards::init();
         DepositPurse::init();
         AcceptableTokens::init();
 
@@ -32,49 +59,370 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         self.set_fee_wallet(fee_wallet);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn create_sell_order(
         &mut self,
         caller: Address,
         start_time: Time,
+        end_time: Option<Time>,
         collection: ContractHash,
         pay_token: Option<ContractHash>,
         tokens: BTreeMap<TokenId, U256>,
+        allowlist_root: Option<[u8; 32]>,
+        reserved_buyer: Option<Address>,
+        custodial: bool,
     ) {
+        if let Some(end_time) = end_time {
+            if end_time <= start_time || end_time <= self.current_block_time() {
+                self.revert(Error::InvalidOrderTime);
+            }
+        }
+
         // Check pay token is acceptable
+        self.fee(pay_token);
 
         tokens.iter().for_each(|(token_id, price)| {
-            let sell_order: SellOrder = SellOrder {
+            self.list_token(SellOrder {
+                id: data::next_order_id(),
                 creator: caller,
                 collection,
                 token_id: *token_id,
                 pay_token,
                 price: *price,
                 start_time,
-                status: 0u8,
-            };
+                end_time,
+                dutch_auction: None,
+                allowlist_root,
+                reserved_buyer,
+                custodial,
+                status: OrderStatus::Pending,
+            });
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_dutch_sell_order(
+        &mut self,
+        caller: Address,
+        start_time: Time,
+        end_time: Option<Time>,
+        collection: ContractHash,
+        pay_token: Option<ContractHash>,
+        token_id: TokenId,
+        start_price: U256,
+        end_price: U256,
+        duration: Time,
+    ) {
+        // The price starts declining once the order is listed at the earliest
+        let start_time = start_time.max(self.current_block_time());
+        if let Some(end_time) = end_time {
+            if end_time <= start_time {
+                self.revert(Error::InvalidOrderTime);
+            }
+        }
+        if duration == 0 || end_price.ge(&start_price) {
+            self.revert(Error::InvalidDutchAuction);
+        }
+        // Check pay token is acceptable
+        self.fee(pay_token);
 
-            let approved = ICEP47::new(collection)
-                .get_approved(caller, *token_id)
-                .unwrap_or_revert_with(Error::RequireApprove);
+        self.list_token(SellOrder {
+            id: data::next_order_id(),
+            creator: caller,
+            collection,
+            token_id,
+            pay_token,
+            price: start_price,
+            start_time,
+            end_time,
+            dutch_auction: Some(DutchAuction {
+                end_price,
+                duration,
+            }),
+            allowlist_root: None,
+            reserved_buyer: None,
+            custodial: true,
+            status: OrderStatus::Pending,
+        });
+    }
 
-            if !approved.eq(&Address::from(self.contract_package_hash())) {
-                self.revert(Error::RequireApprove);
+    fn list_token(&mut self, order: SellOrder) {
+        if order.custodial {
+            self.escrow_token(order.creator, order.collection, order.token_id);
+        } else {
+            self.assert_token_approved(order.creator, order.collection, order.token_id);
+        }
+        // a non-custodial listing may be left behind by a previous owner of the token
+        if let Some(active) = SellOrders::instance().try_get(order.collection, order.token_id) {
+            if !self.is_order_stale(&active) {
+                self.revert(Error::AlreadyExistOrder);
             }
-            ICEP47::new(collection).transfer_from(
-                caller,
-                Address::from(self.contract_package_hash()),
-                vec![*token_id],
-            );
-            SellOrders::instance().set(collection, *token_id, sell_order);
-            self.emit(MarketplaceEvent::SellOrderCreated {
+            self.invalidate_sell_order(active);
+        }
+        SellOrders::instance().set(order);
+        self.emit(MarketplaceEvent::SellOrderCreated {
+            id: order.id,
+            creator: order.creator,
+            collection: order.collection,
+            token_id: order.token_id,
+            pay_token: order.pay_token,
+            price: order.price,
+            start_time: order.start_time,
+            end_time: order.end_time,
+            dutch_auction: order.dutch_auction,
+            allowlist_root: order.allowlist_root,
+            reserved_buyer: order.reserved_buyer,
+            custodial: order.custodial,
+        });
+    }
+
+    // Moves a token the marketplace is approved for into escrow
+    fn escrow_token(&mut self, owner: Address, collection: ContractHash, token_id: TokenId) {
+        self.assert_token_approved(owner, collection, token_id);
+        self.nft(collection).transfer_from(
+            owner,
+            Address::from(self.contract_package_hash()),
+            token_id,
+        );
+    }
+
+    fn assert_token_approved(&self, owner: Address, collection: ContractHash, token_id: TokenId) {
+        let approved = self
+            .nft(collection)
+            .get_approved(owner, token_id)
+            .unwrap_or_revert_with(Error::RequireApprove);
+
+        if !approved.eq(&Address::from(self.contract_package_hash())) {
+            self.revert(Error::RequireApprove);
+        }
+    }
+
+    // Hands a sold or withdrawn token to `recipient`, out of escrow or straight from the creator
+    fn release_token(&mut self, order: &SellOrder, recipient: Address) {
+        if order.custodial {
+            self.nft(order.collection)
+                .transfer(recipient, order.token_id);
+        } else if recipient.ne(&order.creator) {
+            self.nft(order.collection)
+                .transfer_from(order.creator, recipient, order.token_id);
+        }
+    }
+
+    // Closes a non-custodial order whose creator no longer owns or approved the token
+    fn invalidate_sell_order(&mut self, mut order: SellOrder) {
+        order.status = OrderStatus::Invalidated;
+        SellOrders::instance().set(order);
+        self.emit(MarketplaceEvent::SellOrderInvalidated {
+            id: order.id,
+            creator: order.creator,
+            collection: order.collection,
+            token_id: order.token_id,
+            start_time: order.start_time,
+        });
+    }
+
+    fn create_bundle_sell_order(
+        &mut self,
+        caller: Address,
+        items: Vec<Token>,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        start_time: Time,
+        end_time: Option<Time>,
+    ) {
+        if items.is_empty() {
+            self.revert(Error::InvalidBundle);
+        }
+        if let Some(end_time) = end_time {
+            if end_time <= start_time || end_time <= self.current_block_time() {
+                self.revert(Error::InvalidOrderTime);
+            }
+        }
+        // Check pay token is acceptable
+        self.fee(pay_token);
+
+        items.iter().for_each(|(collection, token_id)| {
+            self.escrow_token(caller, *collection, *token_id);
+        });
+
+        let id = data::next_order_id();
+        self.emit(MarketplaceEvent::BundleSellOrderCreated {
+            id,
+            creator: caller,
+            items: items.clone(),
+            pay_token,
+            price,
+            start_time,
+            end_time,
+        });
+        Bundles::instance().set(
+            id,
+            Bundle {
                 creator: caller,
-                collection,
-                token_id: *token_id,
+                items,
                 pay_token,
-                price: *price,
+                price,
                 start_time,
-            });
+                end_time,
+                status: OrderStatus::Pending,
+            },
+        );
+    }
+
+    fn cancel_bundle_sell_order(&mut self, caller: Address, id: u64) {
+        let mut bundle = Bundles::instance().get(id);
+        if bundle.creator.ne(&caller) {
+            self.revert(Error::NotOrderCreator);
+        }
+        if bundle.status != OrderStatus::Pending {
+            self.revert(Error::FinishedOrder);
+        }
+        bundle.items.iter().for_each(|(collection, token_id)| {
+            self.nft(*collection).transfer(caller, *token_id);
+        });
+        bundle.status = OrderStatus::Canceled;
+        Bundles::instance().set(id, bundle);
+        self.emit(MarketplaceEvent::BundleSellOrderCanceled {
+            id,
+            creator: caller,
+        });
+    }
+
+    fn buy_bundle_sell_order_cspr(&mut self, caller: Address, id: u64, amount: U512) {
+        self.assert_valid_cspr_transfer(amount);
+        let mut bundle = Bundles::instance().get(id);
+        self.assert_bundle_is_open(&bundle);
+        if bundle.pay_token.is_some() {
+            self.revert(Error::InvalidPayToken);
+        }
+        let amount_u256 = u512_to_u256(&amount).unwrap();
+        if amount_u256.lt(&bundle.price) {
+            self.revert(Error::InsufficientBalance);
+        }
+
+        bundle.items.iter().for_each(|(collection, token_id)| {
+            self.nft(*collection).transfer(caller, *token_id);
+        });
+        let royalty = self.bundle_item_prices(&bundle).iter().fold(
+            U256::zero(),
+            |royalty, (token, price)| {
+                let item_royalty = self.transfer_cspr_with_fee(
+                    *token,
+                    bundle.creator,
+                    u256_to_512(price).unwrap(),
+                );
+                royalty.checked_add(item_royalty).unwrap_or_revert()
+            },
+        );
+
+        // Refund overpayment
+        let refund = amount
+            .checked_sub(u256_to_512(&bundle.price).unwrap())
+            .unwrap_or_revert();
+        if !refund.is_zero() {
+            self.transfer_cspr(caller, refund);
+        }
+
+        bundle.status = OrderStatus::Completed;
+        self.emit(MarketplaceEvent::BundleSellOrderBought {
+            id,
+            creator: bundle.creator,
+            buyer: caller,
+            price: bundle.price,
+            royalty,
+        });
+        Bundles::instance().set(id, bundle);
+    }
+
+    fn buy_bundle_sell_order(&mut self, caller: Address, id: u64, amount: U256) {
+        let mut bundle = Bundles::instance().get(id);
+        self.assert_bundle_is_open(&bundle);
+        if bundle.pay_token.is_none() {
+            self.revert(Error::InvalidPayToken);
+        }
+        // `amount` is the most the buyer is willing to pay
+        if amount.lt(&bundle.price) {
+            self.revert(Error::InsufficientBalance);
+        }
+        let pay_token = bundle.pay_token.unwrap();
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
+        if allowance.lt(&bundle.price) {
+            self.revert(Error::InsufficientBalance);
+        }
+
+        let royalty = self.bundle_item_prices(&bundle).iter().fold(
+            U256::zero(),
+            |royalty, (token, price)| {
+                let item_royalty =
+                    self.transfer_with_fee(Some(caller), bundle.creator, *token, pay_token, *price);
+                royalty.checked_add(item_royalty).unwrap_or_revert()
+            },
+        );
+        bundle.items.iter().for_each(|(collection, token_id)| {
+            self.nft(*collection).transfer(caller, *token_id);
+        });
+
+        bundle.status = OrderStatus::Completed;
+        self.emit(MarketplaceEvent::BundleSellOrderBought {
+            id,
+            creator: bundle.creator,
+            buyer: caller,
+            price: bundle.price,
+            royalty,
         });
+        Bundles::instance().set(id, bundle);
+    }
+
+    // Splits the bundle price evenly over its items so fees and royalties are settled per
+    // collection, the rounding remainder goes to the first item
+    fn bundle_item_prices(&self, bundle: &Bundle) -> Vec<(Token, U256)> {
+        let count = U256::from(bundle.items.len());
+        let item_price = bundle.price / count;
+        let remainder = bundle.price % count;
+        bundle
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, token)| {
+                if index == 0 {
+                    (*token, item_price + remainder)
+                } else {
+                    (*token, item_price)
+                }
+            })
+            .collect()
+    }
+
+    fn sell_order(&self, collection: ContractHash, token_id: TokenId) -> SellOrder {
+        SellOrders::instance().get(collection, token_id)
+    }
+
+    // Any order ever created, including completed, canceled and invalidated ones
+    fn sell_order_by_id(&self, id: u64) -> SellOrder {
+        SellOrders::instance().get_by_id(id)
+    }
+
+    fn sell_orders_by_seller(&self, seller: Address, offset: u64, limit: u64) -> Vec<SellOrder> {
+        OrderIndex::sellers()
+            .page(Key::from(seller), offset, limit)
+            .iter()
+            .map(|id| SellOrders::instance().get_by_id(*id))
+            .collect()
+    }
+
+    fn sell_orders_by_collection(
+        &self,
+        collection: ContractHash,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<SellOrder> {
+        OrderIndex::collections()
+            .page(Key::from(collection), offset, limit)
+            .iter()
+            .map(|id| SellOrders::instance().get_by_id(*id))
+            .collect()
     }
 
     fn cancel_sell_order(
@@ -84,14 +432,16 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         token_ids: Vec<TokenId>,
     ) {
         token_ids.iter().for_each(|token_id| {
-            let order = SellOrders::instance().get(collection, *token_id);
+            let mut order = SellOrders::instance().get(collection, *token_id);
             if order.creator.ne(&caller) {
                 self.revert(Error::NotOrderCreator);
             }
             self.assert_order_is_active(&order);
-            ICEP47::new(collection).transfer(caller, vec![*token_id]);
-            SellOrders::instance().remove(collection, *token_id);
+            self.release_token(&order, caller);
+            order.status = OrderStatus::Canceled;
+            SellOrders::instance().set(order);
             self.emit(MarketplaceEvent::SellOrderCanceled {
+                id: order.id,
                 creator: order.creator,
                 collection,
                 token_id: *token_id,
@@ -100,6 +450,84 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         });
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn update_sell_order(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        price: U256,
+        pay_token: Option<ContractHash>,
+        start_time: Time,
+        end_time: Option<Time>,
+    ) {
+        let mut order = SellOrders::instance().get(collection, token_id);
+        if order.creator.ne(&caller) {
+            self.revert(Error::NotOrderCreator);
+        }
+        self.assert_order_is_active(&order);
+        if self.is_order_stale(&order) {
+            self.revert(Error::StaleOrder);
+        }
+        // the price of a dutch auction is derived from its schedule
+        if order.dutch_auction.is_some() {
+            self.revert(Error::InvalidDutchAuction);
+        }
+        if let Some(end_time) = end_time {
+            if end_time <= start_time || end_time <= self.current_block_time() {
+                self.revert(Error::InvalidOrderTime);
+            }
+        }
+        // Check pay token is acceptable
+        self.fee(pay_token);
+
+        order.price = price;
+        order.pay_token = pay_token;
+        order.start_time = start_time;
+        order.end_time = end_time;
+        SellOrders::instance().set(order);
+        self.emit(MarketplaceEvent::SellOrderUpdated {
+            id: order.id,
+            creator: caller,
+            collection,
+            token_id,
+            pay_token,
+            price,
+            start_time,
+            end_time,
+        });
+    }
+
+    // Ids without a pending order or whose order hasn't expired yet are skipped, so a single
+    // live order doesn't revert the whole batch
+    fn sweep_expired_sell_orders(&mut self, collection: ContractHash, token_ids: Vec<TokenId>) {
+        for token_id in token_ids {
+            let mut order = match SellOrders::instance().try_get(collection, token_id) {
+                Some(order) if order.status == OrderStatus::Pending => order,
+                _ => continue,
+            };
+            if self.is_order_stale(&order) {
+                self.invalidate_sell_order(order);
+                continue;
+            }
+            if !self.is_order_expired(&order) {
+                continue;
+            }
+            self.release_token(&order, order.creator);
+            order.status = OrderStatus::Expired;
+            SellOrders::instance().set(order);
+            self.emit(MarketplaceEvent::SellOrderCanceled {
+                id: order.id,
+                creator: order.creator,
+                collection,
+                token_id,
+                start_time: order.start_time,
+            });
+        }
+    }
+
+    // Returns without buying when the order is stale, the order is invalidated and `amount`
+    // refunded
     fn buy_sell_order_cspr(
         &mut self,
         caller: Address,
@@ -107,53 +535,74 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         token_id: TokenId,
         amount: U512,
         additional_recipient: Option<Address>,
+        proof: Vec<(String, u8)>,
     ) {
         self.assert_valid_cspr_transfer(amount);
         let mut order = SellOrders::instance().get(collection, token_id);
         self.assert_order_is_active(&order);
+        if self.is_order_stale(&order) {
+            self.invalidate_sell_order(order);
+            self.transfer_cspr(caller, amount);
+            return;
+        }
+        self.assert_order_is_open(&order);
+        self.assert_allowed_buyer(&order, caller, proof);
         if order.pay_token.is_some() {
             self.revert(Error::InvalidPayToken);
         }
+        let price = self.current_price(&order);
         let amount_u256 = u512_to_u256(&amount).unwrap();
-        if amount_u256.lt(&order.price) {
+        if amount_u256.lt(&price) {
             self.revert(Error::InsufficientBalance);
         }
 
+        self.fill_sell_order_cspr(caller, &mut order, price, additional_recipient);
+
+        // Refund overpayment
+        let refund = amount
+            .checked_sub(u256_to_512(&price).unwrap())
+            .unwrap_or_revert();
+        if !refund.is_zero() {
+            self.transfer_cspr(caller, refund);
+        }
+    }
+
+    // Sends the NFT and pays the seller from the deposited CSPR
+    fn fill_sell_order_cspr(
+        &mut self,
+        caller: Address,
+        order: &mut SellOrder,
+        price: U256,
+        additional_recipient: Option<Address>,
+    ) {
+        let collection = order.collection;
+        let token_id = order.token_id;
+
         // Send NFT
-        match additional_recipient {
-            Some(address) => {
-                ICEP47::new(order.collection).transfer(address, vec![token_id]);
-                // ICEP47::new(collection).transfer_from(
-                //     order.creator,
-                //     Address::from(address),
-                //     vec![token_id],
-                // );
-            }
-            None => {
-                ICEP47::new(order.collection).transfer(caller, vec![token_id]);
-                // ICEP47::new(collection).transfer_from(
-                //     order.creator,
-                //     Address::from(caller),
-                //     vec![token_id],
-                // );
-            }
-        };
+        self.release_token(order, additional_recipient.unwrap_or(caller));
 
-        self.transfer_cspr_with_fee(order.creator, amount);
+        let price_u512 = u256_to_512(&price).unwrap();
+        let royalty =
+            self.transfer_cspr_with_fee((collection, token_id), order.creator, price_u512);
 
-        order.status = 1;
+        order.status = OrderStatus::Completed;
 
-        SellOrders::instance().set(collection, token_id, order);
+        SellOrders::instance().set(*order);
         self.emit(MarketplaceEvent::SellOrderBought {
+            id: order.id,
             creator: order.creator,
             collection,
             token_id,
             buyer: caller,
             additional_recipient,
             start_time: order.start_time,
+            price,
+            royalty,
         });
     }
 
+    // Returns without buying when the order is stale, the order is invalidated and no allowance
+    // is pulled. A revert would undo the invalidation and leave the stale order listed
     fn buy_sell_order(
         &mut self,
         caller: Address,
@@ -161,207 +610,1113 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         token_id: TokenId,
         amount: U256,
         additional_recipient: Option<Address>,
+        proof: Vec<(String, u8)>,
     ) {
         let mut order = SellOrders::instance().get(collection, token_id);
         self.assert_order_is_active(&order);
+        if self.is_order_stale(&order) {
+            self.invalidate_sell_order(order);
+            return;
+        }
+        self.assert_order_is_open(&order);
+        self.assert_allowed_buyer(&order, caller, proof);
         if order.pay_token.is_none() {
             self.revert(Error::InvalidPayToken);
         }
-        let allowance = IERC20::new(order.pay_token.unwrap())
+        // `amount` is the most the buyer is willing to pay
+        let price = self.current_price(&order);
+        if amount.lt(&price) {
+            self.revert(Error::InsufficientBalance);
+        }
+        let allowance = self
+            .ft(order.pay_token.unwrap())
             .allowance(caller, Address::from(self.contract_package_hash()));
-        if allowance.lt(&amount) {
+        if allowance.lt(&price) {
             self.revert(Error::InsufficientBalance);
         }
 
+        self.fill_sell_order(caller, &mut order, price, additional_recipient);
+    }
+
+    // Pays the seller from the caller's allowance and sends the NFT
+    fn fill_sell_order(
+        &mut self,
+        caller: Address,
+        order: &mut SellOrder,
+        price: U256,
+        additional_recipient: Option<Address>,
+    ) {
+        let collection = order.collection;
+        let token_id = order.token_id;
+
         // Transfer pay token
-        self.transfer_with_fee(
+        let royalty = self.transfer_with_fee(
             Some(caller),
             order.creator,
+            (collection, token_id),
             order.pay_token.unwrap(),
-            amount,
+            price,
         );
 
         // Send NFT
-        match additional_recipient {
-            Some(address) => {
-                ICEP47::new(order.collection).transfer(address, vec![token_id]);
-                ICEP47::new(collection).transfer_from(order.creator, address, vec![token_id]);
+        self.release_token(order, additional_recipient.unwrap_or(caller));
+
+        order.status = OrderStatus::Completed;
+
+        SellOrders::instance().set(*order);
+        self.emit(MarketplaceEvent::SellOrderBought {
+            id: order.id,
+            creator: order.creator,
+            collection,
+            token_id,
+            buyer: caller,
+            additional_recipient,
+            start_time: order.start_time,
+            price,
+            royalty,
+        });
+    }
+
+    // Buys every listed `(collection, token_id, max_price)` item with the deposited CSPR and
+    // refunds what is left. Unavailable items revert the whole sweep if `all_or_nothing` is set
+    // and are skipped otherwise.
+    fn buy_sell_orders_cspr(
+        &mut self,
+        caller: Address,
+        items: Vec<(ContractHash, TokenId, U256)>,
+        all_or_nothing: bool,
+        amount: U512,
+    ) {
+        self.assert_valid_cspr_transfer(amount);
+        let mut remaining = u512_to_u256(&amount).unwrap();
+        items.iter().for_each(|(collection, token_id, max_price)| {
+            match self.sweepable_order(caller, *collection, *token_id, None, *max_price) {
+                Some((mut order, price)) if price.le(&remaining) => {
+                    self.fill_sell_order_cspr(caller, &mut order, price, None);
+                    remaining = remaining.checked_sub(price).unwrap_or_revert();
+                }
+                _ => {
+                    if all_or_nothing {
+                        self.revert(Error::UnavailableOrder);
+                    }
+                }
             }
-            None => {
-                // ICEP47::new(order.collection).transfer(Address::from(caller), vec![token_id]);
-                ICEP47::new(collection).transfer_from(order.creator, caller, vec![token_id]);
+        });
+
+        // Refund the unspent remainder
+        if !remaining.is_zero() {
+            self.transfer_cspr(caller, u256_to_512(&remaining).unwrap());
+        }
+    }
+
+    // Same as `buy_sell_orders_cspr` for orders priced in `pay_token`, spending at most `amount`
+    // of the caller's allowance
+    fn buy_sell_orders(
+        &mut self,
+        caller: Address,
+        items: Vec<(ContractHash, TokenId, U256)>,
+        pay_token: ContractHash,
+        all_or_nothing: bool,
+        amount: U256,
+    ) {
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
+        if allowance.lt(&amount) {
+            self.revert(Error::InsufficientBalance);
+        }
+        let mut remaining = amount;
+        items.iter().for_each(|(collection, token_id, max_price)| {
+            match self.sweepable_order(caller, *collection, *token_id, Some(pay_token), *max_price)
+            {
+                Some((mut order, price)) if price.le(&remaining) => {
+                    self.fill_sell_order(caller, &mut order, price, None);
+                    remaining = remaining.checked_sub(price).unwrap_or_revert();
+                }
+                _ => {
+                    if all_or_nothing {
+                        self.revert(Error::UnavailableOrder);
+                    }
+                }
             }
-        };
+        });
+    }
+
+    // The order and its current price if `caller` could buy it without a proof right now.
+    // Checked up front because a failing purchase can not be recovered from inside a sweep.
+    fn sweepable_order(
+        &self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        pay_token: Option<ContractHash>,
+        max_price: U256,
+    ) -> Option<(SellOrder, U256)> {
+        let order = SellOrders::instance().try_get(collection, token_id)?;
+        let now = self.current_block_time();
+        if order.status != OrderStatus::Pending
+            || now < order.start_time
+            || self.is_order_expired(&order)
+            || order.pay_token.ne(&pay_token)
+            || order.allowlist_root.is_some()
+            || order
+                .reserved_buyer
+                .map_or(false, |buyer| buyer.ne(&caller))
+        {
+            return None;
+        }
+        let price = self.current_price(&order);
+        if price.gt(&max_price) || self.is_order_stale(&order) {
+            return None;
+        }
+        Some((order, price))
+    }
+
+    fn fulfill_signed_order_cspr(
+        &mut self,
+        caller: Address,
+        order: SignedOrder,
+        public_key: PublicKey,
+        signature: Signature,
+        amount: U512,
+    ) {
+        self.assert_valid_cspr_transfer(amount);
+        if order.pay_token.is_some() {
+            self.revert(Error::InvalidPayToken);
+        }
+        let creator = self.verify_signed_order(&order, &public_key, &signature);
+        let amount_u256 = u512_to_u256(&amount).unwrap();
+        if amount_u256.lt(&order.price) {
+            self.revert(Error::InsufficientBalance);
+        }
+
+        self.nft(order.collection)
+            .transfer_from(creator, caller, order.token_id);
+        let price_u512 = u256_to_512(&order.price).unwrap();
+        let royalty =
+            self.transfer_cspr_with_fee((order.collection, order.token_id), creator, price_u512);
+
+        // Refund overpayment
+        let refund = amount.checked_sub(price_u512).unwrap_or_revert();
+        if !refund.is_zero() {
+            self.transfer_cspr(caller, refund);
+        }
+        // signed orders only get an id once they settle on chain
+        self.emit(MarketplaceEvent::SignedOrderFulfilled {
+            id: data::next_order_id(),
+            creator,
+            collection: order.collection,
+            token_id: order.token_id,
+            buyer: caller,
+            pay_token: None,
+            price: order.price,
+            nonce: order.nonce,
+            royalty,
+        });
+    }
+
+    fn fulfill_signed_order(
+        &mut self,
+        caller: Address,
+        order: SignedOrder,
+        public_key: PublicKey,
+        signature: Signature,
+    ) {
+        let pay_token = order
+            .pay_token
+            .unwrap_or_revert_with(Error::InvalidPayToken);
+        let creator = self.verify_signed_order(&order, &public_key, &signature);
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
+        if allowance.lt(&order.price) {
+            self.revert(Error::InsufficientBalance);
+        }
+
+        let royalty = self.transfer_with_fee(
+            Some(caller),
+            creator,
+            (order.collection, order.token_id),
+            pay_token,
+            order.price,
+        );
+        self.nft(order.collection)
+            .transfer_from(creator, caller, order.token_id);
+        // signed orders only get an id once they settle on chain
+        self.emit(MarketplaceEvent::SignedOrderFulfilled {
+            id: data::next_order_id(),
+            creator,
+            collection: order.collection,
+            token_id: order.token_id,
+            buyer: caller,
+            pay_token: order.pay_token,
+            price: order.price,
+            nonce: order.nonce,
+            royalty,
+        });
+    }
+
+    // Checks the order was signed by `public_key` and can still be filled, consumes its nonce
+    // and returns the seller
+    fn verify_signed_order(
+        &mut self,
+        order: &SignedOrder,
+        public_key: &PublicKey,
+        signature: &Signature,
+    ) -> Address {
+        if self.current_block_time() >= order.expiry {
+            self.revert(Error::ExpiredOrder);
+        }
+        // Check pay token is acceptable
+        self.fee(order.pay_token);
+
+        let creator = Address::from(public_key.to_account_hash());
+        if order.epoch != OrderEpochs::instance().get(creator) {
+            self.revert(Error::InvalidEpoch);
+        }
+        if SignedOrderNonces::instance().is_used(creator, order.nonce) {
+            self.revert(Error::UsedNonce);
+        }
+        let message = order.message(self.contract_package_hash());
+        crypto::verify(message, signature, public_key)
+            .unwrap_or_revert_with(Error::InvalidSignature);
+        SignedOrderNonces::instance().set_used(creator, order.nonce);
+
+        if self
+            .nft(order.collection)
+            .owner_of(order.token_id)
+            .ne(&Some(creator))
+        {
+            self.revert(Error::StaleOrder);
+        }
+        self.assert_token_approved(creator, order.collection, order.token_id);
+        creator
+    }
+
+    // Voids every signed order of the caller at once
+    fn increment_order_epoch(&mut self, caller: Address) {
+        let epoch = OrderEpochs::instance().get(caller) + 1;
+        OrderEpochs::instance().set(caller, epoch);
+        self.emit(MarketplaceEvent::OrderEpochIncremented {
+            creator: caller,
+            epoch,
+        });
+    }
+
+    fn cancel_signed_orders(&mut self, caller: Address, nonces: Vec<u64>) {
+        nonces.iter().for_each(|nonce| {
+            SignedOrderNonces::instance().set_used(caller, *nonce);
+        });
+        self.emit(MarketplaceEvent::SignedOrdersCanceled {
+            creator: caller,
+            nonces,
+        });
+    }
+
+    fn create_buy_order_cspr(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        additional_recipient: Option<Address>,
+        end_time: Option<Time>,
+        amount: U512,
+    ) {
+        self.assert_valid_cspr_transfer(amount);
+        self.assert_valid_end_time(end_time);
+        self.assert_valid_bid_price(u512_to_u256(&amount).unwrap());
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+
+        if bids.contains_key(&caller) {
+            self.revert(Error::AlreadyExistOrder);
+        }
+
+        let buy_order = BuyOrder {
+            id: data::next_order_id(),
+            pay_token: None,
+            price: u512_to_u256(&amount).unwrap(),
+            start_time: self.current_block_time(),
+            end_time,
+            additional_recipient,
+            status: OrderStatus::Pending,
+        };
+        bids.insert(caller, buy_order);
+        OrderIndex::bidders().add(Key::from(caller), (collection, token_id));
+        BuyOrders::instance().set(collection, token_id, bids);
+        self.emit(MarketplaceEvent::BuyOrderCreated {
+            id: buy_order.id,
+            creator: caller,
+            collection,
+            token_id,
+            pay_token: buy_order.pay_token,
+            price: buy_order.price,
+            additional_recipient,
+            start_time: buy_order.start_time,
+            end_time: buy_order.end_time,
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_buy_order(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        additional_recipient: Option<Address>,
+        pay_token: ContractHash,
+        end_time: Option<Time>,
+        amount: U256,
+    ) {
+        self.assert_valid_end_time(end_time);
+        self.assert_valid_bid_price(amount);
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+
+        if bids.contains_key(&caller) {
+            self.revert(Error::AlreadyExistOrder);
+        }
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
+        if allowance.lt(&amount) {
+            self.revert(Error::InsufficientBalance);
+        }
+        self.ft(pay_token).transfer_from(
+            caller,
+            Address::from(self.contract_package_hash()),
+            amount,
+        );
+        let buy_order = BuyOrder {
+            id: data::next_order_id(),
+            pay_token: Some(pay_token),
+            price: amount,
+            start_time: self.current_block_time(),
+            end_time,
+            additional_recipient,
+            status: OrderStatus::Pending,
+        };
+        bids.insert(caller, buy_order);
+        OrderIndex::bidders().add(Key::from(caller), (collection, token_id));
+        BuyOrders::instance().set(collection, token_id, bids);
+        self.emit(MarketplaceEvent::BuyOrderCreated {
+            id: buy_order.id,
+            creator: caller,
+            collection,
+            token_id,
+            pay_token: buy_order.pay_token,
+            price: buy_order.price,
+            additional_recipient,
+            start_time: buy_order.start_time,
+            end_time: buy_order.end_time,
+        });
+    }
+
+    // Tops up the bid with the deposited `amount` or refunds the part of the escrow above `price`
+    fn update_buy_order_cspr(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        price: U256,
+        amount: U512,
+    ) {
+        self.assert_valid_cspr_transfer(amount);
+        self.assert_valid_bid_price(price);
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+        let mut bid = *bids
+            .get(&caller)
+            .unwrap_or_revert_with(Error::NotExistOrder);
+        if bid.pay_token.is_some() {
+            self.revert(Error::InvalidPayToken);
+        }
+        if self.is_buy_order_expired(&bid) {
+            self.revert(Error::ExpiredOrder);
+        }
+        let available = bid
+            .price
+            .checked_add(u512_to_u256(&amount).unwrap())
+            .unwrap_or_revert_with(Error::Overflow);
+        if available.lt(&price) {
+            self.revert(Error::InsufficientBalance);
+        }
+        let refund = available - price;
+        if !refund.is_zero() {
+            self.transfer_cspr(caller, u256_to_512(&refund).unwrap());
+        }
+
+        bid.price = price;
+        bids.insert(caller, bid);
+        BuyOrders::instance().set(collection, token_id, bids);
+        self.emit(MarketplaceEvent::BuyOrderUpdated {
+            id: bid.id,
+            creator: caller,
+            collection,
+            token_id,
+            price,
+        });
+    }
+
+    fn update_buy_order(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        price: U256,
+    ) {
+        self.assert_valid_bid_price(price);
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+        let mut bid = *bids
+            .get(&caller)
+            .unwrap_or_revert_with(Error::NotExistOrder);
+        let pay_token = bid.pay_token.unwrap_or_revert_with(Error::InvalidPayToken);
+        if self.is_buy_order_expired(&bid) {
+            self.revert(Error::ExpiredOrder);
+        }
+        if price.gt(&bid.price) {
+            let top_up = price - bid.price;
+            let allowance = self
+                .ft(pay_token)
+                .allowance(caller, Address::from(self.contract_package_hash()));
+            if allowance.lt(&top_up) {
+                self.revert(Error::InsufficientBalance);
+            }
+            self.ft(pay_token).transfer_from(
+                caller,
+                Address::from(self.contract_package_hash()),
+                top_up,
+            );
+        } else if price.lt(&bid.price) {
+            self.ft(pay_token).transfer(caller, bid.price - price);
+        }
+
+        bid.price = price;
+        bids.insert(caller, bid);
+        BuyOrders::instance().set(collection, token_id, bids);
+        self.emit(MarketplaceEvent::BuyOrderUpdated {
+            id: bid.id,
+            creator: caller,
+            collection,
+            token_id,
+            price,
+        });
+    }
+
+    fn buy_orders(&self, collection: ContractHash, token_id: TokenId) -> Bids {
+        BuyOrders::instance().get(collection, token_id)
+    }
+
+    fn buy_orders_by_bidder(
+        &self,
+        bidder: Address,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<(Token, BuyOrder)> {
+        OrderIndex::bidders()
+            .page(Key::from(bidder), offset, limit)
+            .iter()
+            .filter_map(|(collection, token_id)| {
+                BuyOrders::instance()
+                    .get(*collection, *token_id)
+                    .get(&bidder)
+                    .map(|bid| ((*collection, *token_id), *bid))
+            })
+            .collect()
+    }
+
+    fn cancel_buy_order(&mut self, caller: Address, collection: ContractHash, token_id: TokenId) {
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+
+        match bids.get(&caller) {
+            Some(bid) => {
+                self.refund_buy_order(caller, bid);
+                self.close_buy_order(caller, (collection, token_id), *bid, OrderStatus::Canceled);
+                self.emit(MarketplaceEvent::BuyOrderCanceled {
+                    id: bid.id,
+                    creator: caller,
+                    collection,
+                    token_id,
+                    start_time: bid.start_time,
+                });
+                bids.remove(&caller);
+                BuyOrders::instance().set(collection, token_id, bids);
+            }
+            None => {
+                self.revert(Error::NotExistOrder);
+            }
+        }
+    }
+
+    fn reclaim_expired_buy_orders(&mut self, collection: ContractHash, token_ids: Vec<TokenId>) {
+        token_ids.iter().for_each(|token_id| {
+            let mut bids = BuyOrders::instance().get(collection, *token_id);
+            let expired_bids: Vec<(Address, BuyOrder)> = bids
+                .iter()
+                .filter(|(_, bid)| self.is_buy_order_expired(bid))
+                .map(|(bidder, bid)| (*bidder, *bid))
+                .collect();
+            expired_bids.iter().for_each(|(bidder, bid)| {
+                self.refund_buy_order(*bidder, bid);
+                self.close_buy_order(*bidder, (collection, *token_id), *bid, OrderStatus::Expired);
+                self.emit(MarketplaceEvent::BuyOrderCanceled {
+                    id: bid.id,
+                    creator: *bidder,
+                    collection,
+                    token_id: *token_id,
+                    start_time: bid.start_time,
+                });
+                bids.remove(bidder);
+            });
+            BuyOrders::instance().set(collection, *token_id, bids);
+        });
+    }
+
+    // Keeps a bid leaving the book under its id with the final `status`
+    fn close_buy_order(
+        &mut self,
+        bidder: Address,
+        token: Token,
+        mut bid: BuyOrder,
+        status: OrderStatus,
+    ) {
+        bid.status = status;
+        BuyOrders::instance().set_closed(bid);
+        OrderIndex::bidders().remove(Key::from(bidder), token);
+    }
+
+    fn refund_buy_order(&mut self, bidder: Address, bid: &BuyOrder) {
+        match bid.pay_token {
+            Some(contract_hash) => {
+                self.ft(contract_hash).transfer(bidder, bid.price);
+            }
+            None => {
+                self.transfer_cspr(bidder, u256_to_512(&bid.price).unwrap());
+            }
+        }
+    }
+
+    fn accept_buy_order(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        bidder: Address,
+    ) {
+        let token_owner = self.assert_approved_owner(caller, collection, token_id);
+
+        let mut bids = BuyOrders::instance().get(collection, token_id);
+
+        match bids.get(&bidder) {
+            Some(bid) => {
+                if self.is_buy_order_expired(bid) {
+                    self.revert(Error::ExpiredOrder);
+                }
+                let to = match bid.additional_recipient {
+                    Some(address) => address,
+                    None => caller,
+                };
+                let royalty = match bid.pay_token {
+                    Some(contract_hash) => self.transfer_with_fee(
+                        None,
+                        to,
+                        (collection, token_id),
+                        contract_hash,
+                        bid.price,
+                    ),
+                    None => self.transfer_cspr_with_fee(
+                        (collection, token_id),
+                        to,
+                        u256_to_512(&bid.price).unwrap(),
+                    ),
+                };
+                self.emit(MarketplaceEvent::BuyOrderAccepted {
+                    id: bid.id,
+                    creator: bidder,
+                    collection,
+                    token_id,
+                    start_time: bid.start_time,
+                    owner: token_owner,
+                    royalty,
+                });
+                self.nft(collection).transfer_from(caller, bidder, token_id);
+                self.close_buy_order(bidder, (collection, token_id), *bid, OrderStatus::Completed);
+                bids.remove(&bidder);
+                BuyOrders::instance().set(collection, token_id, bids);
+            }
+            None => {
+                self.revert(Error::NotExistOrder);
+            }
+        }
+    }
+
+    fn create_collection_offer_cspr(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        quantity: u32,
+        end_time: Option<Time>,
+        merkle_root: Option<[u8; 32]>,
+        amount: U512,
+    ) {
+        self.assert_valid_cspr_transfer(amount);
+        let amount = u512_to_u256(&amount).unwrap();
+        // the deposit must cover an equal price for every token
+        if quantity == 0 || !(amount % U256::from(quantity)).is_zero() {
+            self.revert(Error::InvalidQuantity);
+        }
+        let price = amount / U256::from(quantity);
+        self.add_collection_offer(
+            caller,
+            collection,
+            None,
+            price,
+            quantity,
+            end_time,
+            merkle_root,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_collection_offer(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        pay_token: ContractHash,
+        price: U256,
+        quantity: u32,
+        end_time: Option<Time>,
+        merkle_root: Option<[u8; 32]>,
+    ) {
+        if quantity == 0 {
+            self.revert(Error::InvalidQuantity);
+        }
+        let amount = price
+            .checked_mul(U256::from(quantity))
+            .unwrap_or_revert_with(Error::Overflow);
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
+        if allowance.lt(&amount) {
+            self.revert(Error::InsufficientBalance);
+        }
+        self.ft(pay_token).transfer_from(
+            caller,
+            Address::from(self.contract_package_hash()),
+            amount,
+        );
+        self.add_collection_offer(
+            caller,
+            collection,
+            Some(pay_token),
+            price,
+            quantity,
+            end_time,
+            merkle_root,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_collection_offer(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        pay_token: Option<ContractHash>,
+        price: U256,
+        quantity: u32,
+        end_time: Option<Time>,
+        merkle_root: Option<[u8; 32]>,
+    ) {
+        self.assert_valid_end_time(end_time);
+        // Check pay token is acceptable
+        self.fee(pay_token);
+        if CollectionOffers::instance()
+            .get(collection, caller)
+            .is_some()
+        {
+            self.revert(Error::AlreadyExistOrder);
+        }
+
+        let offer = CollectionOffer {
+            id: data::next_order_id(),
+            pay_token,
+            price,
+            quantity,
+            start_time: self.current_block_time(),
+            end_time,
+            merkle_root,
+            status: OrderStatus::Pending,
+        };
+        self.emit(MarketplaceEvent::CollectionOfferCreated {
+            id: offer.id,
+            creator: caller,
+            collection,
+            pay_token,
+            price,
+            quantity,
+            start_time: offer.start_time,
+            end_time,
+            merkle_root,
+        });
+        CollectionOffers::instance().set(collection, caller, offer);
+    }
+
+    fn cancel_collection_offer(&mut self, caller: Address, collection: ContractHash) {
+        let mut offer = CollectionOffers::instance()
+            .get(collection, caller)
+            .unwrap_or_revert_with(Error::NotExistOrder);
+
+        self.refund_collection_offer(caller, &offer);
+        self.emit(MarketplaceEvent::CollectionOfferCanceled {
+            id: offer.id,
+            creator: caller,
+            collection,
+            quantity: offer.quantity,
+        });
+        offer.status = OrderStatus::Canceled;
+        CollectionOffers::instance().set_closed(collection, caller, offer);
+    }
+
+    fn refund_collection_offer(&mut self, bidder: Address, offer: &CollectionOffer) {
+        let amount = offer.price * U256::from(offer.quantity);
+        match offer.pay_token {
+            Some(contract_hash) => {
+                self.ft(contract_hash).transfer(bidder, amount);
+            }
+            None => {
+                self.transfer_cspr(bidder, u256_to_512(&amount).unwrap());
+            }
+        }
+    }
+
+    fn accept_collection_offer(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        bidder: Address,
+        proof: Vec<(String, u8)>,
+    ) {
+        let token_owner = self.assert_approved_owner(caller, collection, token_id);
+
+        let mut offer = CollectionOffers::instance()
+            .get(collection, bidder)
+            .unwrap_or_revert_with(Error::NotExistOrder);
+        if self.is_collection_offer_expired(&offer) {
+            self.revert(Error::ExpiredOrder);
+        }
+        // leaves of trait offers are decimal token ids
+        if let Some(merkle_root) = offer.merkle_root {
+            merkle_tree::verify(hex::encode(merkle_root), token_id.to_string(), proof);
+        }
+
+        let royalty = match offer.pay_token {
+            Some(contract_hash) => self.transfer_with_fee(
+                None,
+                caller,
+                (collection, token_id),
+                contract_hash,
+                offer.price,
+            ),
+            None => self.transfer_cspr_with_fee(
+                (collection, token_id),
+                caller,
+                u256_to_512(&offer.price).unwrap(),
+            ),
+        };
+        self.nft(collection).transfer_from(caller, bidder, token_id);
+
+        offer.quantity -= 1;
+        self.emit(MarketplaceEvent::CollectionOfferAccepted {
+            id: offer.id,
+            creator: bidder,
+            collection,
+            token_id,
+            owner: token_owner,
+            price: offer.price,
+            remaining: offer.quantity,
+            royalty,
+        });
+        if offer.quantity == 0 {
+            offer.status = OrderStatus::Completed;
+            CollectionOffers::instance().set_closed(collection, bidder, offer);
+        } else {
+            CollectionOffers::instance().set(collection, bidder, offer);
+        }
+    }
+
+    // Returns the owner after checking it is `caller` and the marketplace is approved
+    fn assert_approved_owner(
+        &self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+    ) -> Address {
+        let token_owner = self
+            .nft(collection)
+            .owner_of(token_id)
+            .unwrap_or_revert_with(Error::NotExistToken);
+        if caller.ne(&token_owner) {
+            self.revert(Error::NotTokenOwner);
+        }
+
+        let approved = self
+            .nft(collection)
+            .get_approved(caller, token_id)
+            .unwrap_or_revert_with(Error::RequireApprove);
+
+        if !approved.eq(&Address::from(self.contract_package_hash())) {
+            self.revert(Error::RequireApprove);
+        }
+        token_owner
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_auction(
+        &mut self,
+        caller: Address,
+        collection: ContractHash,
+        token_id: TokenId,
+        pay_token: Option<ContractHash>,
+        reserve_price: U256,
+        min_bid_increment: U256,
+        start_time: Time,
+        end_time: Time,
+        extension_window: Time,
+    ) {
+        if end_time <= start_time || end_time <= self.current_block_time() {
+            self.revert(Error::InvalidOrderTime);
+        }
+        if min_bid_increment.is_zero() {
+            self.revert(Error::InvalidBidIncrement);
+        }
+        if extension_window > self.max_auction_extension() {
+            self.revert(Error::InvalidExtensionWindow);
+        }
+        // Check pay token is acceptable
+        self.fee(pay_token);
 
-        order.status = 1;
+        self.escrow_token(caller, collection, token_id);
 
-        SellOrders::instance().set(collection, token_id, order);
-        self.emit(MarketplaceEvent::SellOrderBought {
-            creator: order.creator,
+        let auction = Auction {
+            id: data::next_order_id(),
+            creator: caller,
             collection,
             token_id,
-            buyer: caller,
-            additional_recipient,
-            start_time: order.start_time,
+            pay_token,
+            reserve_price,
+            min_bid_increment,
+            start_time,
+            end_time,
+            extension_window,
+            highest_bidder: None,
+            highest_bid: U256::zero(),
+            status: OrderStatus::Pending,
+        };
+        Auctions::instance().set(collection, token_id, auction);
+        self.emit(MarketplaceEvent::AuctionCreated {
+            id: auction.id,
+            creator: caller,
+            collection,
+            token_id,
+            pay_token,
+            reserve_price,
+            min_bid_increment,
+            start_time,
+            end_time,
+            extension_window,
         });
     }
 
-    fn create_buy_order_cspr(
+    fn place_bid_cspr(
         &mut self,
         caller: Address,
         collection: ContractHash,
         token_id: TokenId,
-        additional_recipient: Option<Address>,
         amount: U512,
     ) {
         self.assert_valid_cspr_transfer(amount);
-        let mut bids = BuyOrders::instance().get(collection, token_id);
-
-        if bids.contains_key(&caller) {
-            self.revert(Error::AlreadyExistOrder);
+        let mut auction = Auctions::instance().get(collection, token_id);
+        if auction.pay_token.is_some() {
+            self.revert(Error::InvalidPayToken);
         }
+        let amount_u256 = u512_to_u256(&amount).unwrap();
+        self.assert_valid_bid(&auction, amount_u256);
 
-        let buy_order = BuyOrder {
-            pay_token: None,
-            price: u512_to_u256(&amount).unwrap(),
-            start_time: self.current_block_time(),
-            additional_recipient,
-        };
-        bids.insert(caller, buy_order);
-        BuyOrders::instance().set(collection, token_id, bids);
-        self.emit(MarketplaceEvent::BuyOrderCreated {
-            creator: caller,
+        // Refund outbid bidder
+        self.refund_auction_bid(&auction);
+        self.extend_auction(&mut auction);
+
+        auction.highest_bidder = Some(caller);
+        auction.highest_bid = amount_u256;
+        Auctions::instance().set(collection, token_id, auction);
+        self.emit(MarketplaceEvent::AuctionBid {
+            id: auction.id,
             collection,
             token_id,
-            pay_token: buy_order.pay_token,
-            price: buy_order.price,
-            additional_recipient,
-            start_time: buy_order.start_time,
+            bidder: caller,
+            amount: amount_u256,
         });
     }
 
-    fn create_buy_order(
+    fn place_bid(
         &mut self,
         caller: Address,
         collection: ContractHash,
         token_id: TokenId,
-        additional_recipient: Option<Address>,
-        pay_token: ContractHash,
         amount: U256,
     ) {
-        let mut bids = BuyOrders::instance().get(collection, token_id);
-
-        if bids.contains_key(&caller) {
-            self.revert(Error::AlreadyExistOrder);
+        let mut auction = Auctions::instance().get(collection, token_id);
+        if auction.pay_token.is_none() {
+            self.revert(Error::InvalidPayToken);
         }
-        let allowance =
-            IERC20::new(pay_token).allowance(caller, Address::from(self.contract_package_hash()));
+        self.assert_valid_bid(&auction, amount);
+
+        let pay_token = auction.pay_token.unwrap();
+        let allowance = self
+            .ft(pay_token)
+            .allowance(caller, Address::from(self.contract_package_hash()));
         if allowance.lt(&amount) {
             self.revert(Error::InsufficientBalance);
         }
-        IERC20::new(pay_token).transfer_from(
+        self.ft(pay_token).transfer_from(
             caller,
             Address::from(self.contract_package_hash()),
             amount,
         );
-        let buy_order = BuyOrder {
-            pay_token: Some(pay_token),
-            price: amount,
-            start_time: self.current_block_time(),
-            additional_recipient,
-        };
-        bids.insert(caller, buy_order);
-        BuyOrders::instance().set(collection, token_id, bids);
-        self.emit(MarketplaceEvent::BuyOrderCreated {
-            creator: caller,
+
+        // Refund outbid bidder
+        self.refund_auction_bid(&auction);
+        self.extend_auction(&mut auction);
+
+        auction.highest_bidder = Some(caller);
+        auction.highest_bid = amount;
+        Auctions::instance().set(collection, token_id, auction);
+        self.emit(MarketplaceEvent::AuctionBid {
+            id: auction.id,
             collection,
             token_id,
-            pay_token: buy_order.pay_token,
-            price: buy_order.price,
-            additional_recipient,
-            start_time: buy_order.start_time,
+            bidder: caller,
+            amount,
         });
     }
 
-    fn cancel_buy_order(&mut self, caller: Address, collection: ContractHash, token_id: TokenId) {
-        let mut bids = BuyOrders::instance().get(collection, token_id);
+    fn settle_auction(&mut self, collection: ContractHash, token_id: TokenId) {
+        let mut auction = Auctions::instance().get(collection, token_id);
+        if auction.status != OrderStatus::Pending {
+            self.revert(Error::FinishedAuction);
+        }
+        if self.current_block_time() < auction.end_time {
+            self.revert(Error::NotFinishedAuction);
+        }
 
-        match bids.get(&caller) {
-            Some(bid) => {
-                match bid.pay_token {
-                    Some(contract_hash) => {
-                        IERC20::new(contract_hash).transfer(caller, bid.price);
-                    }
-                    None => {
-                        self.transfer_cspr(caller, u256_to_512(&bid.price).unwrap());
-                    }
+        let royalty = match auction.highest_bidder {
+            Some(winner) => {
+                self.nft(collection).transfer(winner, token_id);
+                match auction.pay_token {
+                    Some(contract_hash) => self.transfer_with_fee(
+                        None,
+                        auction.creator,
+                        (collection, token_id),
+                        contract_hash,
+                        auction.highest_bid,
+                    ),
+                    None => self.transfer_cspr_with_fee(
+                        (collection, token_id),
+                        auction.creator,
+                        u256_to_512(&auction.highest_bid).unwrap(),
+                    ),
                 }
-                self.emit(MarketplaceEvent::BuyOrderCanceled {
-                    creator: caller,
-                    collection,
-                    token_id,
-                    start_time: bid.start_time,
-                });
-                bids.remove(&caller);
-                BuyOrders::instance().set(collection, token_id, bids);
             }
             None => {
-                self.revert(Error::NotExistOrder);
+                // No bid reached the reserve price
+                self.nft(collection).transfer(auction.creator, token_id);
+                U256::zero()
             }
-        }
+        };
+
+        auction.status = OrderStatus::Completed;
+        Auctions::instance().set_closed(auction);
+        self.emit(MarketplaceEvent::AuctionSettled {
+            id: auction.id,
+            creator: auction.creator,
+            collection,
+            token_id,
+            winner: auction.highest_bidder,
+            amount: auction.highest_bid,
+            royalty,
+        });
     }
 
-    fn accept_buy_order(
-        &mut self,
-        caller: Address,
-        collection: ContractHash,
-        token_id: TokenId,
-        bidder: Address,
-    ) {
-        let token_owner = ICEP47::new(collection)
-            .owner_of(token_id)
-            .unwrap_or_revert_with(Error::NotExistToken);
-        if caller.ne(&token_owner) {
-            self.revert(Error::NotTokenOwner);
+    fn cancel_auction(&mut self, caller: Address, collection: ContractHash, token_id: TokenId) {
+        let mut auction = Auctions::instance().get(collection, token_id);
+        if auction.creator.ne(&caller) {
+            self.revert(Error::NotOrderCreator);
         }
+        if auction.status != OrderStatus::Pending {
+            self.revert(Error::FinishedAuction);
+        }
+        if auction.highest_bidder.is_some() {
+            self.revert(Error::AlreadyBidAuction);
+        }
+        self.nft(collection).transfer(caller, token_id);
+        auction.status = OrderStatus::Canceled;
+        Auctions::instance().set_closed(auction);
+        self.emit(MarketplaceEvent::AuctionCanceled {
+            id: auction.id,
+            creator: caller,
+            collection,
+            token_id,
+        });
+    }
 
-        let approved = ICEP47::new(collection)
-            .get_approved(caller, token_id)
-            .unwrap_or_revert_with(Error::RequireApprove);
-
-        if !approved.eq(&Address::from(self.contract_package_hash())) {
-            self.revert(Error::RequireApprove);
+    fn assert_valid_bid(&self, auction: &Auction, amount: U256) {
+        if auction.status != OrderStatus::Pending {
+            self.revert(Error::FinishedAuction);
+        }
+        let now = self.current_block_time();
+        if now < auction.start_time {
+            self.revert(Error::NotStartedAuction);
         }
+        if now >= auction.end_time {
+            self.revert(Error::FinishedAuction);
+        }
+        let min_bid = match auction.highest_bidder {
+            Some(_) => auction
+                .highest_bid
+                .checked_add(auction.min_bid_increment)
+                .unwrap_or_revert_with(Error::Overflow),
+            None => auction.reserve_price,
+        };
+        if amount.lt(&min_bid) {
+            self.revert(Error::InsufficientBid);
+        }
+    }
 
-        let mut bids = BuyOrders::instance().get(collection, token_id);
+    fn extend_auction(&mut self, auction: &mut Auction) {
+        // Bids placed within the last `extension_window` push the end time out so that the
+        // window is always left for counter bids.
+        let now = self.current_block_time();
+        if now.saturating_add(auction.extension_window) <= auction.end_time {
+            return;
+        }
+        auction.end_time = now
+            .checked_add(auction.extension_window)
+            .unwrap_or_revert_with(Error::Overflow);
+        self.emit(MarketplaceEvent::AuctionExtended {
+            id: auction.id,
+            collection: auction.collection,
+            token_id: auction.token_id,
+            end_time: auction.end_time,
+        });
+    }
 
-        match bids.get(&bidder) {
-            Some(bid) => {
-                let to = match bid.additional_recipient {
-                    Some(address) => address,
-                    None => caller,
-                };
-                match bid.pay_token {
-                    Some(contract_hash) => {
-                        self.transfer_with_fee(None, to, contract_hash, bid.price)
-                    }
-                    None => {
-                        self.transfer_cspr_with_fee(to, u256_to_512(&bid.price).unwrap());
-                    }
+    fn refund_auction_bid(&mut self, auction: &Auction) {
+        if let Some(bidder) = auction.highest_bidder {
+            match auction.pay_token {
+                Some(contract_hash) => {
+                    self.ft(contract_hash).transfer(bidder, auction.highest_bid);
+                }
+                None => {
+                    self.transfer_cspr(bidder, u256_to_512(&auction.highest_bid).unwrap());
                 }
-                self.emit(MarketplaceEvent::BuyOrderAccepted {
-                    creator: bidder,
-                    collection,
-                    token_id,
-                    start_time: bid.start_time,
-                    owner: token_owner,
-                });
-                ICEP47::new(collection).transfer_from(caller, bidder, vec![token_id]);
-                bids.remove(&bidder);
-                BuyOrders::instance().set(collection, token_id, bids);
-            }
-            None => {
-                self.revert(Error::NotExistOrder);
             }
         }
     }
@@ -370,57 +1725,139 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         &self,
         from: Option<Address>,
         to: Address,
+        token: Token,
         contract_hash: ContractHash,
         amount: U256,
-    ) {
+    ) -> U256 {
         let fee = U256::from(self.fee(Some(contract_hash)));
+        let royalty_shares = self.royalty_shares(token, amount);
+        let royalty_amount = royalty_shares
+            .iter()
+            .fold(U256::zero(), |total, (_, share)| {
+                total.checked_add(*share).unwrap_or_revert()
+            });
         let fee_denominator = U256::exp10(4);
-        let transfer_amount_to_account = amount
-            .checked_mul(fee_denominator.checked_sub(fee).unwrap_or_revert())
-            .unwrap_or_revert()
-            .checked_div(fee_denominator)
-            .unwrap_or_revert();
-
         let transfer_amount_to_fee_wallet = amount
             .checked_mul(fee)
             .unwrap_or_revert()
             .checked_div(fee_denominator)
             .unwrap_or_revert();
+        // The seller gets the rest, so the rounding dust of the fee and royalties isn't lost
+        let transfer_amount_to_account = amount
+            .checked_sub(transfer_amount_to_fee_wallet)
+            .unwrap_or_revert()
+            .checked_sub(royalty_amount)
+            .unwrap_or_revert_with(Error::InvalidRoyalty);
         let fee_wallet = self.fee_wallet();
         match from {
             Some(address) => {
-                IERC20::new(contract_hash).transfer_from(address, to, transfer_amount_to_account);
-                IERC20::new(contract_hash).transfer_from(
+                self.ft(contract_hash)
+                    .transfer_from(address, to, transfer_amount_to_account);
+                self.ft(contract_hash).transfer_from(
                     address,
                     fee_wallet,
                     transfer_amount_to_fee_wallet,
                 );
+                royalty_shares.iter().for_each(|(recipient, share)| {
+                    self.ft(contract_hash)
+                        .transfer_from(address, *recipient, *share);
+                });
             }
             None => {
-                IERC20::new(contract_hash).transfer(to, transfer_amount_to_account);
-                IERC20::new(contract_hash).transfer(fee_wallet, transfer_amount_to_fee_wallet);
+                self.ft(contract_hash)
+                    .transfer(to, transfer_amount_to_account);
+                self.ft(contract_hash)
+                    .transfer(fee_wallet, transfer_amount_to_fee_wallet);
+                royalty_shares.iter().for_each(|(recipient, share)| {
+                    self.ft(contract_hash).transfer(*recipient, *share);
+                });
             }
         }
+        royalty_amount
     }
 
-    fn transfer_cspr_with_fee(&mut self, account: Address, amount: U512) {
+    fn transfer_cspr_with_fee(&mut self, token: Token, account: Address, amount: U512) -> U256 {
         let fee = U512::from(self.fee(None));
+        let royalty_shares = self.royalty_shares(token, u512_to_u256(&amount).unwrap());
+        let royalty_amount = royalty_shares
+            .iter()
+            .fold(U256::zero(), |total, (_, share)| {
+                total.checked_add(*share).unwrap_or_revert()
+            });
         let fee_denominator = U512::exp10(4);
-        let transfer_amount_to_account = amount
-            .checked_mul(fee_denominator.checked_sub(fee).unwrap_or_revert())
-            .unwrap_or_revert()
-            .checked_div(fee_denominator)
-            .unwrap_or_revert();
-
         let transfer_amount_to_fee_wallet = amount
             .checked_mul(fee)
             .unwrap_or_revert()
             .checked_div(fee_denominator)
             .unwrap_or_revert();
+        // The seller gets the rest, so the rounding dust of the fee and royalties isn't lost
+        let transfer_amount_to_account = amount
+            .checked_sub(transfer_amount_to_fee_wallet)
+            .unwrap_or_revert()
+            .checked_sub(u256_to_512(&royalty_amount).unwrap())
+            .unwrap_or_revert_with(Error::InvalidRoyalty);
         let fee_wallet = self.fee_wallet();
 
         self.transfer_cspr(account, transfer_amount_to_account);
         self.transfer_cspr(fee_wallet, transfer_amount_to_fee_wallet);
+        royalty_shares.iter().for_each(|(recipient, share)| {
+            self.transfer_cspr(*recipient, u256_to_512(share).unwrap());
+        });
+        royalty_amount
+    }
+
+    // Royalty shares owed on a sale of `token` for `amount`. Collections registered as
+    // implementing `royalty_info` are asked directly, the rest use the local registry.
+    fn royalty_shares(&self, token: Token, amount: U256) -> Vec<(Address, U256)> {
+        let (collection, token_id) = token;
+        let fee_denominator = U256::exp10(4);
+        if self.royalty_info_supported(collection) {
+            let (recipient, royalty_amount) =
+                IRoyalty::new(collection).royalty_info(token_id, amount);
+            let max_royalty_amount = amount
+                .checked_mul(U256::from(MAX_ROYALTY_BPS))
+                .unwrap_or_revert()
+                .checked_div(fee_denominator)
+                .unwrap_or_revert();
+            if royalty_amount.gt(&max_royalty_amount) {
+                self.revert(Error::InvalidRoyalty);
+            }
+            if royalty_amount.is_zero() {
+                return Vec::new();
+            }
+            return vec![(recipient, royalty_amount)];
+        }
+
+        // Every share is rounded down and the rounding dust goes to the first share, so the
+        // total always equals `amount * royalty_bps / 10000`.
+        let total = amount
+            .checked_mul(U256::from(self.royalty_bps(collection)))
+            .unwrap_or_revert()
+            .checked_div(fee_denominator)
+            .unwrap_or_revert();
+        let mut shares: Vec<(Address, U256)> = self
+            .royalty(collection)
+            .iter()
+            .map(|share| {
+                let share_amount = amount
+                    .checked_mul(U256::from(share.bps))
+                    .unwrap_or_revert()
+                    .checked_div(fee_denominator)
+                    .unwrap_or_revert();
+                (share.recipient, share_amount)
+            })
+            .collect();
+        let distributed = shares.iter().fold(U256::zero(), |total, (_, share)| {
+            total.checked_add(*share).unwrap_or_revert()
+        });
+        if let Some(first) = shares.first_mut() {
+            first.1 = first
+                .1
+                .checked_add(total.checked_sub(distributed).unwrap_or_revert())
+                .unwrap_or_revert();
+        }
+        shares.retain(|(_, share)| !share.is_zero());
+        shares
     }
 
     fn transfer_cspr(&mut self, account: Address, amount: U512) {
@@ -488,6 +1925,29 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         AcceptableTokens::instance().remove(token);
     }
 
+    fn is_acceptable_token(&self, token: Option<ContractHash>) -> bool {
+        AcceptableTokens::instance().contains(token.unwrap_or_else(|| ContractHash::new([0u8; 32])))
+    }
+
+    // CSPR is listed as the null contract hash
+    fn acceptable_tokens(&self) -> Vec<ContractHash> {
+        AcceptableTokens::instance().list()
+    }
+
+    fn set_token_standard(&mut self, token: ContractHash, standard: FTStandard) {
+        // only acceptable tokens can be registered
+        self.fee(Some(token));
+        AcceptableTokens::instance().set_standard(token, standard);
+    }
+
+    fn token_standard(&self, token: ContractHash) -> FTStandard {
+        AcceptableTokens::instance().get_standard(token)
+    }
+
+    fn ft(&self, token: ContractHash) -> IFT {
+        IFT::new(token, self.token_standard(token))
+    }
+
     fn set_fee_wallet(&mut self, wallet: Address) {
         data::set_fee_wallet(wallet);
     }
@@ -496,6 +1956,66 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
         data::get_fee_wallet()
     }
 
+    fn set_max_auction_extension(&mut self, window: Time) {
+        data::set_max_auction_extension(window);
+    }
+
+    fn max_auction_extension(&self) -> Time {
+        data::get_max_auction_extension()
+    }
+
+    fn set_collection_admin(&mut self, collection: ContractHash, admin: Address) {
+        CollectionAdmins::instance().set(collection, admin);
+    }
+
+    fn collection_admin(&self, collection: ContractHash) -> Option<Address> {
+        CollectionAdmins::instance().get(collection)
+    }
+
+    fn set_collection_standard(&mut self, collection: ContractHash, standard: NFTStandard) {
+        CollectionStandards::instance().set(collection, standard);
+    }
+
+    fn collection_standard(&self, collection: ContractHash) -> NFTStandard {
+        CollectionStandards::instance().get(collection)
+    }
+
+    fn nft(&self, collection: ContractHash) -> INFT {
+        INFT::new(
+            collection,
+            self.collection_standard(collection),
+            Address::from(self.contract_package_hash()),
+        )
+    }
+
+    fn set_royalty(&mut self, collection: ContractHash, shares: Vec<Royalty>) {
+        let total_bps = shares
+            .iter()
+            .try_fold(0u32, |total, share| total.checked_add(share.bps));
+        match total_bps {
+            Some(total_bps) if total_bps <= MAX_ROYALTY_BPS => {}
+            _ => self.revert(Error::InvalidRoyalty),
+        }
+        Royalties::instance().set(collection, shares.clone());
+        self.emit(MarketplaceEvent::RoyaltyUpdated { collection, shares });
+    }
+
+    fn royalty(&self, collection: ContractHash) -> Vec<Royalty> {
+        Royalties::instance().get(collection)
+    }
+
+    fn set_royalty_info_supported(&mut self, collection: ContractHash, supported: bool) {
+        RoyaltyInfoCollections::instance().set(collection, supported);
+    }
+
+    fn royalty_info_supported(&self, collection: ContractHash) -> bool {
+        RoyaltyInfoCollections::instance().get(collection)
+    }
+
+    fn royalty_bps(&self, collection: ContractHash) -> u32 {
+        self.royalty(collection).iter().map(|share| share.bps).sum()
+    }
+
     fn _check_offer_is_acceptable(&self) {}
 
     fn current_block_time(&self) -> u64 {
@@ -507,16 +2027,119 @@ pub trait Marketplace<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn assert_order_is_active(&self, order: &SellOrder) {
-        if order.status == 1u8 {
+        if order.status != OrderStatus::Pending {
             runtime::revert(Error::FinishedOrder)
         }
+    }
+
+    // A non-custodial order can no longer be filled once the token left the creator's wallet
+    // or the marketplace approval was revoked
+    fn is_order_stale(&self, order: &SellOrder) -> bool {
+        if order.custodial {
+            return false;
+        }
+        let nft = self.nft(order.collection);
+        let owner = nft.owner_of(order.token_id);
+        if owner.ne(&Some(order.creator)) {
+            return true;
+        }
+        nft.get_approved(order.creator, order.token_id)
+            .ne(&Some(Address::from(self.contract_package_hash())))
+    }
+
+    fn assert_order_is_open(&self, order: &SellOrder) {
+        if self.current_block_time() < order.start_time {
+            self.revert(Error::NotStartedOrder);
+        }
+        if self.is_order_expired(order) {
+            self.revert(Error::ExpiredOrder);
+        }
+    }
+
+    fn assert_allowed_buyer(&self, order: &SellOrder, buyer: Address, proof: Vec<(String, u8)>) {
+        if let Some(reserved_buyer) = order.reserved_buyer {
+            if reserved_buyer.ne(&buyer) {
+                self.revert(Error::NotReservedBuyer);
+            }
+        }
+        if let Some(allowlist_root) = order.allowlist_root {
+            merkle_tree::verify(
+                hex::encode(allowlist_root),
+                Key::from(buyer).to_formatted_string(),
+                proof,
+            );
+        }
+    }
+
+    fn assert_bundle_is_open(&self, bundle: &Bundle) {
+        if bundle.status != OrderStatus::Pending {
+            self.revert(Error::FinishedOrder);
+        }
+        if self.current_block_time() < bundle.start_time {
+            self.revert(Error::NotStartedOrder);
+        }
+        if let Some(end_time) = bundle.end_time {
+            if self.current_block_time() >= end_time {
+                self.revert(Error::ExpiredOrder);
+            }
+        }
+    }
+
+    fn current_price(&self, order: &SellOrder) -> U256 {
+        match order.dutch_auction {
+            Some(dutch_auction) => {
+                let elapsed = self.current_block_time().saturating_sub(order.start_time);
+                if elapsed >= dutch_auction.duration {
+                    return dutch_auction.end_price;
+                }
+                let decline = order
+                    .price
+                    .checked_sub(dutch_auction.end_price)
+                    .unwrap_or_revert()
+                    .checked_mul(U256::from(elapsed))
+                    .unwrap_or_revert_with(Error::Overflow)
+                    .checked_div(U256::from(dutch_auction.duration))
+                    .unwrap_or_revert();
+                order.price.checked_sub(decline).unwrap_or_revert()
+            }
+            None => order.price,
+        }
+    }
+
+    fn is_order_expired(&self, order: &SellOrder) -> bool {
+        match order.end_time {
+            Some(end_time) => self.current_block_time() >= end_time,
+            None => false,
+        }
+    }
+
+    fn is_buy_order_expired(&self, order: &BuyOrder) -> bool {
+        match order.end_time {
+            Some(end_time) => self.current_block_time() >= end_time,
+            None => false,
+        }
+    }
+
+    fn is_collection_offer_expired(&self, offer: &CollectionOffer) -> bool {
+        match offer.end_time {
+            Some(end_time) => self.current_block_time() >= end_time,
+            None => false,
+        }
+    }
+
+    fn assert_valid_end_time(&self, end_time: Option<Time>) {
+        if let Some(end_time) = end_time {
+            if end_time <= self.current_block_time() {
+                self.revert(Error::InvalidOrderTime);
+            }
+        }
+    }
 
-        // let token_owner = ICEP47::new(order.collection)
-        //     .owner_of(order.token_id)
-        //     .unwrap();
-        // if token_owner.ne(&Address::from(self.contract_package_hash())) {
-        //     runtime::revert(Error::FinishedOrder);
-        // }
+    // A zero bid is a cancel in disguise and would linger in the book with nothing escrowed
+    fn assert_valid_bid_price(&self, price: U256) {
+        if price.is_zero() {
+            self.revert(Error::InvalidPrice);
+        }
     }
 
     fn store_result<T: CLTyped + ToBytes>(&mut self, value: T) {
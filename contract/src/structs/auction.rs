@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+use casper_types::{ContractHash, U256};
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+
+use crate::{Address, OrderStatus, Time, TokenId};
+
+#[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct Auction {
+    pub id: u64,
+    pub creator: Address,
+    pub collection: ContractHash,
+    pub token_id: TokenId,
+    pub pay_token: Option<ContractHash>,
+    pub reserve_price: U256,
+    pub min_bid_increment: U256,
+    pub start_time: Time,
+    pub end_time: Time,
+    pub extension_window: Time,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: U256,
+    pub status: OrderStatus,
+}
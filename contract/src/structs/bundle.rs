@@ -0,0 +1,17 @@
+use alloc::vec::Vec;
+use casper_types::{ContractHash, U256};
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+
+use crate::{Address, OrderStatus, Time, Token};
+
+// one price covers every item, the items are bought together or not at all
+#[derive(Clone, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct Bundle {
+    pub creator: Address,
+    pub items: Vec<Token>,
+    pub pay_token: Option<ContractHash>,
+    pub price: U256,
+    pub start_time: Time,
+    pub end_time: Option<Time>,
+    pub status: OrderStatus,
+}
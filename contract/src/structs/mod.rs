@@ -1 +1,4 @@
+pub mod auction;
+pub mod bundle;
 pub mod order;
+pub mod royalty;
@@ -1,26 +1,77 @@
-use alloc::vec::Vec;
-use casper_types::{ContractHash, U256};
+use alloc::{string::String, vec::Vec};
+use casper_types::{bytesrepr::ToBytes, ContractHash, ContractPackageHash, U256};
 use casper_types_derive::{CLTyped, FromBytes, ToBytes};
 
-use crate::{Address, Time, TokenId};
-
-// order status u8 0:pending, 1:completed, 2:canceled
+use crate::{Address, OrderStatus, Time, TokenId};
 
 #[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
 pub struct SellOrder {
+    pub id: u64,
     pub creator: Address,
     pub collection: ContractHash,
     pub token_id: TokenId,
     pub pay_token: Option<ContractHash>,
     pub price: U256,
     pub start_time: Time,
-    pub status: u8,
+    pub end_time: Option<Time>,
+    pub dutch_auction: Option<DutchAuction>,
+    // merkle root of the buyers allowed to purchase, leaves are formatted `Key` strings
+    pub allowlist_root: Option<[u8; 32]>,
+    // the only address allowed to buy a private listing
+    pub reserved_buyer: Option<Address>,
+    // false if the token stays in the creator's wallet until the order is filled
+    pub custodial: bool,
+    pub status: OrderStatus,
+}
+
+// price declines linearly from `SellOrder.price` at `start_time` to `end_price` over `duration`
+#[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct DutchAuction {
+    pub end_price: U256,
+    pub duration: Time,
 }
 
 #[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
 pub struct BuyOrder {
+    pub id: u64,
     pub pay_token: Option<ContractHash>,
     pub price: U256,
     pub start_time: Time,
+    pub end_time: Option<Time>,
     pub additional_recipient: Option<Address>,
+    pub status: OrderStatus,
+}
+
+// collection-wide buy order, `price` is paid per token and escrowed for the remaining `quantity`
+// with a `merkle_root` only token ids proven to be leaves of the tree are eligible (trait offers)
+#[derive(Clone, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct CollectionOffer {
+    pub id: u64,
+    pub pay_token: Option<ContractHash>,
+    pub price: U256,
+    pub quantity: u32,
+    pub start_time: Time,
+    pub end_time: Option<Time>,
+    pub merkle_root: Option<[u8; 32]>,
+    pub status: OrderStatus,
+}
+
+// sell order signed off-chain by the token owner and settled by the buyer, `nonce` is single use
+// and the order is void once the owner moves past `epoch`
+#[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct SignedOrder {
+    pub collection: ContractHash,
+    pub token_id: TokenId,
+    pub price: U256,
+    pub pay_token: Option<ContractHash>,
+    pub expiry: Time,
+    pub nonce: u64,
+    pub epoch: u64,
+}
+
+impl SignedOrder {
+    // the bytes the seller signs, bound to one marketplace package
+    pub fn message(&self, marketplace: ContractPackageHash) -> Vec<u8> {
+        (marketplace, *self).to_bytes().unwrap()
+    }
 }
@@ -0,0 +1,13 @@
+use alloc::vec::Vec;
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+
+use crate::Address;
+
+// A single share of a collection's royalty.
+// bps is denominated in 1/10000 of the sale price, same as the marketplace fee
+
+#[derive(Clone, Copy, Debug, CLTyped, ToBytes, FromBytes)]
+pub struct Royalty {
+    pub recipient: Address,
+    pub bps: u32,
+}
@@ -16,7 +16,7 @@ import {
 import { CEP47Client } from "casper-cep47-js-client";
 import { BigNumberish, parseFixed } from "@ethersproject/bignumber";
 import { getAccountNamedKeyValue, getDeploy, getBinary } from "./utils";
-import { MarketplaceClient } from "./clients/marketplace";
+import { MarketplaceClient, toCLProof } from "./clients/marketplace";
 import { Some, None } from "ts-results";
 const {
   fromCLMap,
@@ -40,7 +40,7 @@ const {
   MARKETPLACE_CONTRACT,
   MINT_ONE_PAYMENT_AMOUNT,
   DEFAULT_ENTRYPOINT_PAYMENT_AMOUNT,
-  PRE_ORDER_CSPR_CONTRACT,
+  PRE_BUY_SELL_ORDER_CSPR_CONTRACT,
 } = process.env;
 
 const private_key = Keys.Ed25519.parsePrivateKeyFile(
@@ -78,13 +78,13 @@ const testCancelSellOrder = async () => {
     marketplace_contract: CLValueBuilder.string(
       `contract-${contractHash.slice(5)}`
     ),
-    entrypoint: CLValueBuilder.string("buy_sell_order_cspr"),
     collection: CLValueBuilder.string(`contract-${nftContractHash.slice(5)}`),
     token_id: CLValueBuilder.u256(41),
     amount: CLValueBuilder.u512("50000000000"),
     additional_recipient: additionalRecipient
       ? CLValueBuilder.option(Some(additionalRecipient))
       : CLValueBuilder.option(None, new CLKeyType()),
+    proof: toCLProof(),
   });
   const deployHash = await installContract(
     CHAIN_NAME!,
@@ -92,7 +92,7 @@ const testCancelSellOrder = async () => {
     KEYS,
     runtimeArgs,
     "100000000000",
-    PRE_ORDER_CSPR_CONTRACT!
+    PRE_BUY_SELL_ORDER_CSPR_CONTRACT!
   );
   console.log({ deployHash });
   await getDeploy(NODE_ADDRESS!, deployHash);
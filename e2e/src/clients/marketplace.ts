@@ -19,6 +19,9 @@ import {
   CLU64Type,
   CLU32Type,
   CLU256Type,
+  CLU8Type,
+  CLList,
+  CLTuple2Type,
 } from "casper-js-sdk";
 import { BigNumberish } from "@ethersproject/bignumber";
 import { Some, None } from "ts-results";
@@ -97,6 +100,22 @@ export const MarketplaceEventParser = (
   return null;
 };
 
+// merkle proof items are (hex encoded hash, position) where position 0 puts the item on the
+// left of the computed hash and 1 on the right
+export type MerkleProof = [string, number][];
+
+export const toCLProof = (proof: MerkleProof = []) =>
+  proof.length
+    ? CLValueBuilder.list(
+        proof.map(([hash, position]) =>
+          CLValueBuilder.tuple2([
+            CLValueBuilder.string(hash),
+            CLValueBuilder.u8(position),
+          ])
+        )
+      )
+    : new CLList(new CLTuple2Type([new CLStringType(), new CLU8Type()]));
+
 export class MarketplaceClient {
   casperClient: CasperClient;
   contractClient: Contracts.Contract;
@@ -150,7 +169,11 @@ export class MarketplaceClient {
     tokens: Map<BigNumberish, BigNumberish>,
     key: Keys.AsymmetricKey,
     paymentAmount: string,
-    payToken?: string
+    payToken?: string,
+    endTime?: number,
+    allowlistRoot?: string,
+    reservedBuyer?: CLKeyParameters,
+    custodial = true
   ) {
     const tokensMap = new CLMap([new CLU256Type(), new CLU256Type()]);
     Array.from(tokens.entries()).forEach((token) => {
@@ -162,11 +185,21 @@ export class MarketplaceClient {
 
     const runtimeArgs = RuntimeArgs.fromMap({
       start_time: CLValueBuilder.u64(startTime),
+      end_time: endTime
+        ? CLValueBuilder.option(Some(CLValueBuilder.u64(endTime)))
+        : CLValueBuilder.option(None, new CLU64Type()),
       collection: CLValueBuilder.string(collection),
       tokens: tokensMap,
       pay_token: payToken
         ? CLValueBuilder.option(Some(CLValueBuilder.string(payToken)))
         : CLValueBuilder.option(None, new CLStringType()),
+      allowlist_root: allowlistRoot
+        ? CLValueBuilder.option(Some(CLValueBuilder.string(allowlistRoot)))
+        : CLValueBuilder.option(None, new CLStringType()),
+      reserved_buyer: reservedBuyer
+        ? CLValueBuilder.option(Some(new CLKey(reservedBuyer)))
+        : CLValueBuilder.option(None, new CLKeyType()),
+      custodial: CLValueBuilder.bool(custodial),
     });
 
     return this.contractClient.callEntrypoint(
@@ -206,7 +239,8 @@ export class MarketplaceClient {
     amount: BigNumberish,
     key: Keys.AsymmetricKey,
     paymentAmount: string,
-    additionalReccipient?: CLKeyParameters
+    additionalReccipient?: CLKeyParameters,
+    proof?: MerkleProof
   ) {
     const runtimeArgs = RuntimeArgs.fromMap({
       collection: CLValueBuilder.string(collection),
@@ -215,6 +249,7 @@ export class MarketplaceClient {
       additional_recipient: additionalReccipient
         ? CLValueBuilder.option(Some(additionalReccipient))
         : CLValueBuilder.option(None, new CLKeyType()),
+      proof: toCLProof(proof),
     });
     return this.contractClient.callEntrypoint(
       "buy_sell_order",
@@ -258,18 +293,23 @@ export class MarketplaceClient {
     payToken: string,
     key: Keys.AsymmetricKey,
     paymentAmount: string,
-    additionalReccipient?: CLKeyParameters
+    additionalReccipient?: CLKeyParameters,
+    endTime?: number
   ) {
     const runtimeArgs = RuntimeArgs.fromMap({
       collection: CLValueBuilder.string(collection),
       token_id: CLValueBuilder.u256(tokenId),
       amount: CLValueBuilder.u256(amount),
+      pay_token: CLValueBuilder.string(payToken),
       additional_recipient: additionalReccipient
         ? CLValueBuilder.option(Some(additionalReccipient))
         : CLValueBuilder.option(None, new CLKeyType()),
+      end_time: endTime
+        ? CLValueBuilder.option(Some(CLValueBuilder.u64(endTime)))
+        : CLValueBuilder.option(None, new CLU64Type()),
     });
     return this.contractClient.callEntrypoint(
-      "cancel_sell_order",
+      "create_buy_order",
       runtimeArgs,
       key.publicKey,
       this.networkName,
@@ -7,6 +7,7 @@ import {
   decodeBase16,
   RuntimeArgs,
   CLKeyType,
+  CLU64Type,
 } from "casper-js-sdk";
 import {
   CasperContractClient,
@@ -78,13 +79,14 @@ const testCancelSellOrder = async () => {
     marketplace_contract: CLValueBuilder.string(
       `contract-${contractHash.slice(5)}`
     ),
-    entrypoint: CLValueBuilder.string("buy_sell_order_cspr"),
+    entrypoint: CLValueBuilder.string("create_buy_order_cspr"),
     collection: CLValueBuilder.string(`contract-${nftContractHash.slice(5)}`),
     token_id: CLValueBuilder.u256(41),
     amount: CLValueBuilder.u512("50000000000"),
     additional_recipient: additionalRecipient
       ? CLValueBuilder.option(Some(additionalRecipient))
       : CLValueBuilder.option(None, new CLKeyType()),
+    end_time: CLValueBuilder.option(None, new CLU64Type()),
   });
   const deployHash = await installContract(
     CHAIN_NAME!,
@@ -0,0 +1,280 @@
+#![no_main]
+#![no_std]
+
+// Minimal CEP-18 token for the marketplace tests. Owners, spenders and recipients are `Key`s,
+// `approve` replaces the allowance and `transfer_from` consumes it. The whole supply is minted
+// to the installing account.
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::NamedKeys,
+    ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Key, Parameter, URef, U256,
+};
+use kunftmarketplace_contract::get_immediate_caller_address;
+
+const BALANCES_DICT: &str = "balances";
+const ALLOWANCES_DICT: &str = "allowances";
+const NAME_KEY: &str = "name";
+const SYMBOL_KEY: &str = "symbol";
+const DECIMALS_KEY: &str = "decimals";
+const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+// the error codes of the CEP-18 reference implementation
+#[repr(u16)]
+enum Error {
+    InsufficientBalance = 60001,
+    InsufficientAllowance = 60002,
+    Overflow = 60003,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+fn named_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert()
+}
+
+fn dict(name: &str) -> URef {
+    *runtime::get_key(name)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+// dictionary item keys are the hex encoded blake2b hash of the serialized keys
+fn item_key(keys: &[Key]) -> String {
+    let mut preimage = vec![];
+    for key in keys {
+        preimage.append(&mut key.to_bytes().unwrap_or_revert());
+    }
+    runtime::blake2b(preimage)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn read_balance(owner: Key) -> U256 {
+    storage::dictionary_get(dict(BALANCES_DICT), &item_key(&[owner]))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn read_allowance(owner: Key, spender: Key) -> U256 {
+    storage::dictionary_get(dict(ALLOWANCES_DICT), &item_key(&[owner, spender]))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn write_allowance(owner: Key, spender: Key, amount: U256) {
+    storage::dictionary_put(dict(ALLOWANCES_DICT), &item_key(&[owner, spender]), amount);
+}
+
+fn transfer_balance(owner: Key, recipient: Key, amount: U256) {
+    let owner_balance = read_balance(owner)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientBalance);
+    storage::dictionary_put(dict(BALANCES_DICT), &item_key(&[owner]), owner_balance);
+    let recipient_balance = read_balance(recipient)
+        .checked_add(amount)
+        .unwrap_or_revert_with(Error::Overflow);
+    storage::dictionary_put(
+        dict(BALANCES_DICT),
+        &item_key(&[recipient]),
+        recipient_balance,
+    );
+}
+
+fn caller() -> Key {
+    Key::from(get_immediate_caller_address().unwrap_or_revert())
+}
+
+fn ret_named_key<T: CLTyped + FromBytes + ToBytes>(name: &str) {
+    let value: T = storage::read(named_uref(name))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn name() {
+    ret_named_key::<String>(NAME_KEY);
+}
+
+#[no_mangle]
+pub extern "C" fn symbol() {
+    ret_named_key::<String>(SYMBOL_KEY);
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    ret_named_key::<u8>(DECIMALS_KEY);
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    ret_named_key::<U256>(TOTAL_SUPPLY_KEY);
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Key = runtime::get_named_arg("address");
+    runtime::ret(CLValue::from_t(read_balance(address)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn allowance() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let spender: Key = runtime::get_named_arg("spender");
+    runtime::ret(CLValue::from_t(read_allowance(owner, spender)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: Key = runtime::get_named_arg("spender");
+    let amount: U256 = runtime::get_named_arg("amount");
+    write_allowance(caller(), spender, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+    transfer_balance(caller(), recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let spender = caller();
+    let allowance = read_allowance(owner, spender)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(Error::InsufficientAllowance);
+    write_allowance(owner, spender, allowance);
+    transfer_balance(owner, recipient, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    let name: String = runtime::get_named_arg("name");
+    let symbol: String = runtime::get_named_arg("symbol");
+    let decimals: u8 = runtime::get_named_arg("decimals");
+    let total_supply: U256 = runtime::get_named_arg("total_supply");
+
+    let mut named_keys = NamedKeys::new();
+    let balances = storage::new_dictionary(BALANCES_DICT).unwrap_or_revert();
+    storage::dictionary_put(
+        balances,
+        &item_key(&[Key::from(runtime::get_caller())]),
+        total_supply,
+    );
+    runtime::remove_key(BALANCES_DICT);
+    named_keys.insert(BALANCES_DICT.to_string(), balances.into());
+    let allowances = storage::new_dictionary(ALLOWANCES_DICT).unwrap_or_revert();
+    runtime::remove_key(ALLOWANCES_DICT);
+    named_keys.insert(ALLOWANCES_DICT.to_string(), allowances.into());
+    named_keys.insert(NAME_KEY.to_string(), storage::new_uref(name).into());
+    named_keys.insert(SYMBOL_KEY.to_string(), storage::new_uref(symbol).into());
+    named_keys.insert(DECIMALS_KEY.to_string(), storage::new_uref(decimals).into());
+    named_keys.insert(
+        TOTAL_SUPPLY_KEY.to_string(),
+        storage::new_uref(total_supply).into(),
+    );
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        Some(named_keys),
+        Some(format!("{}_contract_package_hash", contract_name)),
+        Some(format!("{}_contract_access_token", contract_name)),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    for (name, ret) in [
+        ("name", CLType::String),
+        ("symbol", CLType::String),
+        ("decimals", CLType::U8),
+        ("total_supply", CLType::U256),
+    ]
+    .iter()
+    {
+        entry_points.add_entry_point(EntryPoint::new(
+            *name,
+            vec![],
+            ret.clone(),
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("address", CLType::Key)],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allowance",
+        vec![
+            Parameter::new("owner", CLType::Key),
+            Parameter::new("spender", CLType::Key),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", CLType::Key),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", CLType::Key),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", CLType::Key),
+            Parameter::new("recipient", CLType::Key),
+            Parameter::new("amount", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
@@ -0,0 +1,256 @@
+#![no_main]
+#![no_std]
+
+// Minimal CEP-78 collection for the marketplace tests. Tokens are minted with an explicit
+// identifier and only the entry points the marketplace calls are exposed, including the
+// `royalty_info` extension with a single recipient anyone can reconfigure.
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::NamedKeys, ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Key, Parameter, URef, U256,
+};
+use kunftmarketplace_contract::get_immediate_caller_address;
+
+const OWNERS_DICT: &str = "owners";
+const APPROVALS_DICT: &str = "approvals";
+const IDENTIFIER_MODE_KEY: &str = "identifier_mode";
+const ROYALTY_RECIPIENT_KEY: &str = "royalty_recipient";
+const ROYALTY_BPS_KEY: &str = "royalty_bps";
+
+#[repr(u16)]
+enum Error {
+    TokenAlreadyMinted = 1,
+    InvalidTokenIdentifier,
+    InvalidTokenOwner,
+    PermissionDenied,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+fn named_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert()
+}
+
+fn dict(name: &str) -> URef {
+    *runtime::get_key(name)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+// identifier mode 0: ordinal `token_id: u64`, 1: hash `token_hash: String`
+fn token_identifier() -> String {
+    let mode: u8 = storage::read(named_uref(IDENTIFIER_MODE_KEY))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    match mode {
+        0 => runtime::get_named_arg::<u64>("token_id").to_string(),
+        _ => runtime::get_named_arg("token_hash"),
+    }
+}
+
+fn owner_of_token(identifier: &str) -> Key {
+    storage::dictionary_get(dict(OWNERS_DICT), identifier)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(Error::InvalidTokenIdentifier)
+}
+
+fn approved_of_token(identifier: &str) -> Option<Key> {
+    storage::dictionary_get(dict(APPROVALS_DICT), identifier)
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn caller() -> Key {
+    Key::from(get_immediate_caller_address().unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn mint() {
+    let token_owner: Key = runtime::get_named_arg("token_owner");
+    let identifier = token_identifier();
+    if storage::dictionary_get::<Key>(dict(OWNERS_DICT), &identifier)
+        .unwrap_or_revert()
+        .is_some()
+    {
+        runtime::revert(Error::TokenAlreadyMinted);
+    }
+    storage::dictionary_put(dict(OWNERS_DICT), &identifier, token_owner);
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let operator: Key = runtime::get_named_arg("operator");
+    let identifier = token_identifier();
+    if owner_of_token(&identifier) != caller() {
+        runtime::revert(Error::InvalidTokenOwner);
+    }
+    storage::dictionary_put(dict(APPROVALS_DICT), &identifier, Some(operator));
+}
+
+#[no_mangle]
+pub extern "C" fn get_approved() {
+    let identifier = token_identifier();
+    runtime::ret(CLValue::from_t(approved_of_token(&identifier)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn owner_of() {
+    let identifier = token_identifier();
+    runtime::ret(CLValue::from_t(owner_of_token(&identifier)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let source_key: Key = runtime::get_named_arg("source_key");
+    let target_key: Key = runtime::get_named_arg("target_key");
+    let identifier = token_identifier();
+    let owner = owner_of_token(&identifier);
+    if owner != source_key {
+        runtime::revert(Error::InvalidTokenOwner);
+    }
+    let caller = caller();
+    if caller != owner && Some(caller) != approved_of_token(&identifier) {
+        runtime::revert(Error::PermissionDenied);
+    }
+    storage::dictionary_put(dict(OWNERS_DICT), &identifier, target_key);
+    storage::dictionary_put(dict(APPROVALS_DICT), &identifier, Option::<Key>::None);
+    runtime::ret(CLValue::from_t((identifier, target_key)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_royalty_info() {
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let bps: u32 = runtime::get_named_arg("bps");
+    storage::write(named_uref(ROYALTY_RECIPIENT_KEY), recipient);
+    storage::write(named_uref(ROYALTY_BPS_KEY), bps);
+}
+
+// the same share of `sale_price` for every token
+#[no_mangle]
+pub extern "C" fn royalty_info() {
+    let sale_price: U256 = runtime::get_named_arg("sale_price");
+    let recipient: Key = storage::read(named_uref(ROYALTY_RECIPIENT_KEY))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    let bps: u32 = storage::read(named_uref(ROYALTY_BPS_KEY))
+        .unwrap_or_revert()
+        .unwrap_or_revert();
+    let royalty_amount = sale_price
+        .checked_mul(U256::from(bps))
+        .unwrap_or_revert()
+        .checked_div(U256::exp10(4))
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t((recipient, royalty_amount)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    let identifier_mode: u8 = runtime::get_named_arg("identifier_mode");
+
+    let mut named_keys = NamedKeys::new();
+    for name in [OWNERS_DICT, APPROVALS_DICT].iter() {
+        let dict = storage::new_dictionary(name).unwrap_or_revert();
+        runtime::remove_key(name);
+        named_keys.insert(name.to_string(), dict.into());
+    }
+    named_keys.insert(
+        IDENTIFIER_MODE_KEY.to_string(),
+        storage::new_uref(identifier_mode).into(),
+    );
+    named_keys.insert(
+        ROYALTY_RECIPIENT_KEY.to_string(),
+        storage::new_uref(Key::from(runtime::get_caller())).into(),
+    );
+    named_keys.insert(ROYALTY_BPS_KEY.to_string(), storage::new_uref(0u32).into());
+
+    let (contract_hash, _) = storage::new_contract(
+        get_entry_points(),
+        Some(named_keys),
+        Some(format!("{}_contract_package_hash", contract_name)),
+        Some(format!("{}_contract_access_token", contract_name)),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint",
+        vec![Parameter::new("token_owner", CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![Parameter::new("operator", CLType::Key)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_approved",
+        vec![],
+        Option::<Key>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "owner_of",
+        vec![],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("source_key", CLType::Key),
+            Parameter::new("target_key", CLType::Key),
+        ],
+        <(String, Key)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_royalty_info",
+        vec![
+            Parameter::new("recipient", CLType::Key),
+            Parameter::new("bps", CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "royalty_info",
+        vec![
+            Parameter::new("token_id", CLType::U256),
+            Parameter::new("sale_price", CLType::U256),
+        ],
+        <(Key, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
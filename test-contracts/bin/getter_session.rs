@@ -0,0 +1,90 @@
+#![no_main]
+#![no_std]
+
+// Calls a marketplace getter and stores its return value under the `result` named key of the
+// calling account, so that tests can read what the getter returned to a contract caller.
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    runtime_args, ApiError, CLTyped, ContractHash, RuntimeArgs, U256,
+};
+use kunftmarketplace_contract::{Address, Bids, BuyOrder, SellOrder, Token};
+
+const RESULT_KEY: &str = "result";
+
+fn call_getter<T: CLTyped + FromBytes>(entry_point: &str, args: RuntimeArgs) -> T {
+    let marketplace_contract: ContractHash = {
+        let marketplace_contract_str: String = runtime::get_named_arg("marketplace_contract");
+        ContractHash::from_formatted_str(&marketplace_contract_str).unwrap_or_revert()
+    };
+    runtime::call_contract(marketplace_contract, entry_point, args)
+}
+
+fn store_result<T: CLTyped + ToBytes>(value: T) {
+    runtime::put_key(RESULT_KEY, storage::new_uref(value).into());
+}
+
+fn page_args<T: CLTyped + FromBytes + ToBytes>(owner_arg: &str) -> RuntimeArgs {
+    runtime_args! {
+        owner_arg => runtime::get_named_arg::<T>(owner_arg),
+        "offset" => runtime::get_named_arg::<u64>("offset"),
+        "limit" => runtime::get_named_arg::<u64>("limit"),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_point: String = runtime::get_named_arg("entry_point");
+    match entry_point.as_str() {
+        "get_sell_order" | "get_buy_orders" => {
+            let args = runtime_args! {
+                "collection" => runtime::get_named_arg::<String>("collection"),
+                "token_id" => runtime::get_named_arg::<U256>("token_id"),
+            };
+            if entry_point == "get_sell_order" {
+                store_result(call_getter::<SellOrder>(&entry_point, args))
+            } else {
+                store_result(call_getter::<Bids>(&entry_point, args))
+            }
+        }
+        "get_sell_order_by_id" => store_result(call_getter::<SellOrder>(
+            &entry_point,
+            runtime_args! { "id" => runtime::get_named_arg::<u64>("id") },
+        )),
+        "get_fee" | "is_acceptable_token" => {
+            let args = runtime_args! {
+                "token" => runtime::get_named_arg::<Option<String>>("token"),
+            };
+            if entry_point == "get_fee" {
+                store_result(call_getter::<u32>(&entry_point, args))
+            } else {
+                store_result(call_getter::<bool>(&entry_point, args))
+            }
+        }
+        "get_fee_wallet" => store_result(call_getter::<Address>(&entry_point, runtime_args! {})),
+        "list_acceptable_tokens" => store_result(call_getter::<Vec<ContractHash>>(
+            &entry_point,
+            runtime_args! {},
+        )),
+        "get_sell_orders_by_seller" => store_result(call_getter::<Vec<SellOrder>>(
+            &entry_point,
+            page_args::<Address>("seller"),
+        )),
+        "get_sell_orders_by_collection" => store_result(call_getter::<Vec<SellOrder>>(
+            &entry_point,
+            page_args::<String>("collection"),
+        )),
+        "get_buy_orders_by_bidder" => store_result(call_getter::<Vec<(Token, BuyOrder)>>(
+            &entry_point,
+            page_args::<Address>("bidder"),
+        )),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
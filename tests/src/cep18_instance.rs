@@ -0,0 +1,81 @@
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, runtime_args, ContractHash, Key, RuntimeArgs, U256,
+};
+use test_env::{TestContract, TestEnv};
+
+// same item keys as the mock: the hex encoded blake2b hash of the serialized keys
+fn item_key(keys: &[Key]) -> String {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    keys.iter()
+        .for_each(|key| hasher.update(key.to_bytes().unwrap()));
+    hex::encode(hasher.finalize_boxed())
+}
+
+// mock CEP-18 token, the whole supply is minted to `sender`
+pub struct CEP18Instance(TestContract);
+
+impl CEP18Instance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        symbol: &str,
+        decimals: u8,
+        total_supply: U256,
+    ) -> CEP18Instance {
+        CEP18Instance(TestContract::new(
+            env,
+            "cep18_token.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "name" => String::from(contract_name),
+                "symbol" => String::from(symbol),
+                "decimals" => decimals,
+                "total_supply" => total_supply,
+            },
+        ))
+    }
+
+    pub fn contract_hash(&self) -> ContractHash {
+        self.0.contract_hash()
+    }
+
+    pub fn balance_of(&self, owner: Key) -> U256 {
+        self.0
+            .query_dictionary("balances", item_key(&[owner]))
+            .unwrap_or_default()
+    }
+
+    pub fn allowance(&self, owner: Key, spender: Key) -> U256 {
+        self.0
+            .query_dictionary("allowances", item_key(&[owner, spender]))
+            .unwrap_or_default()
+    }
+
+    pub fn approve(&self, sender: AccountHash, spender: Key, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "approve",
+            runtime_args! {
+                "spender" => spender,
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn transfer(&self, sender: AccountHash, recipient: Key, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient,
+                "amount" => amount
+            },
+        )
+    }
+}
@@ -0,0 +1,66 @@
+use casper_types::{
+    account::AccountHash, runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs,
+};
+use test_env::{TestContract, TestEnv};
+
+// mock CEP-78 collection in the `Ordinal` identifier mode
+pub struct CEP78Instance(TestContract);
+
+impl CEP78Instance {
+    pub fn new(env: &TestEnv, contract_name: &str, sender: AccountHash) -> CEP78Instance {
+        CEP78Instance(TestContract::new(
+            env,
+            "cep78_token.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "identifier_mode" => 0u8
+            },
+        ))
+    }
+
+    pub fn contract_package_hash(&self) -> ContractPackageHash {
+        self.0.contract_package_hash()
+    }
+
+    pub fn contract_hash(&self) -> ContractHash {
+        self.0.contract_hash()
+    }
+
+    pub fn mint<T: Into<Key>>(&self, sender: AccountHash, token_owner: T, token_id: u64) {
+        self.0.call_contract(
+            sender,
+            "mint",
+            runtime_args! {
+                "token_owner" => token_owner.into(),
+                "token_id" => token_id
+            },
+        )
+    }
+
+    pub fn approve<T: Into<Key>>(&self, sender: AccountHash, operator: T, token_id: u64) {
+        self.0.call_contract(
+            sender,
+            "approve",
+            runtime_args! {
+                "operator" => operator.into(),
+                "token_id" => token_id
+            },
+        )
+    }
+
+    pub fn set_royalty_info<T: Into<Key>>(&self, sender: AccountHash, recipient: T, bps: u32) {
+        self.0.call_contract(
+            sender,
+            "set_royalty_info",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "bps" => bps
+            },
+        )
+    }
+
+    pub fn owner_of(&self, token_id: u64) -> Option<Key> {
+        self.0.query_dictionary("owners", token_id.to_string())
+    }
+}
@@ -5,9 +5,15 @@ extern crate alloc;
 #[cfg(test)]
 pub mod marketplace_instance;
 
+#[cfg(test)]
+pub mod cep18_instance;
+
 #[cfg(test)]
 pub mod cep47_instance;
 
+#[cfg(test)]
+pub mod cep78_instance;
+
 #[cfg(test)]
 pub mod marketplace_test;
 
@@ -1,11 +1,13 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, time::SystemTime};
 
-use crate::utils::key_and_value_to_str;
+use crate::utils::{key_and_value_to_str, key_to_str};
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, runtime_args, CLTyped, ContractHash,
-    ContractPackageHash, Key, RuntimeArgs, U256,
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, ApiError, CLTyped, ContractHash,
+    ContractPackageHash, Key, PublicKey, RuntimeArgs, U256, U512,
+};
+use kunftmarketplace_contract::{
+    Address, Auction, Bids, Bundle, CollectionOffer, SellOrder, SignedOrder, Time, TokenId,
 };
-use kunftmarketplace_contract::{Address, Bids, SellOrder, Time, TokenId};
 use test_env::{TestContract, TestEnv};
 
 pub struct MarketplaceInstance(TestContract);
@@ -32,22 +34,60 @@ impl MarketplaceInstance {
         ))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_sell_order(
         &self,
         sender: AccountHash,
         start_time: Time,
+        end_time: Option<Time>,
         collection: String,
         tokens: BTreeMap<TokenId, U256>,
         pay_token: Option<String>,
+        allowlist_root: Option<String>,
+        reserved_buyer: Option<Address>,
+        custodial: bool,
     ) {
         self.0.call_contract(
             sender,
             "create_sell_order",
             runtime_args! {
                 "start_time" => start_time,
+                "end_time" => end_time,
                 "collection" => collection,
                 "tokens" => tokens,
                 "pay_token" => pay_token,
+                "allowlist_root" => allowlist_root,
+                "reserved_buyer" => reserved_buyer,
+                "custodial" => custodial,
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_dutch_sell_order(
+        &self,
+        sender: AccountHash,
+        start_time: Time,
+        collection: String,
+        token_id: TokenId,
+        pay_token: Option<String>,
+        start_price: U256,
+        end_price: U256,
+        duration: Time,
+    ) {
+        let end_time: Option<Time> = None;
+        self.0.call_contract(
+            sender,
+            "create_dutch_sell_order",
+            runtime_args! {
+                "start_time" => start_time,
+                "end_time" => end_time,
+                "collection" => collection,
+                "pay_token" => pay_token,
+                "token_id" => token_id,
+                "start_price" => start_price,
+                "end_price" => end_price,
+                "duration" => duration,
             },
         )
     }
@@ -68,13 +108,274 @@ impl MarketplaceInstance {
         )
     }
 
-    pub fn buy_sell_order(&self, sender: AccountHash, collection: String, token_id: U256) {
+    pub fn sweep_expired_sell_orders(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_ids: Vec<TokenId>,
+        time: SystemTime,
+    ) {
+        self.0.call_contract_with_time(
+            sender,
+            "sweep_expired_sell_orders",
+            runtime_args! {
+                "collection" => collection,
+                "token_ids" => token_ids,
+            },
+            time,
+        )
+    }
+
+    pub fn sweep_expired_sell_orders_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_ids: Vec<TokenId>,
+        time: SystemTime,
+        error: E,
+    ) {
+        self.0.call_contract_with_error(
+            sender,
+            "sweep_expired_sell_orders",
+            runtime_args! {
+                "collection" => collection,
+                "token_ids" => token_ids,
+            },
+            time,
+            error,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_sell_order(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        price: U256,
+        pay_token: Option<String>,
+        start_time: Time,
+        end_time: Option<Time>,
+    ) {
         self.0.call_contract(
+            sender,
+            "update_sell_order",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "price" => price,
+                "pay_token" => pay_token,
+                "start_time" => start_time,
+                "end_time" => end_time,
+            },
+        )
+    }
+
+    pub fn buy_sell_order(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: U256,
+        amount: U256,
+        additional_recipient: Option<Address>,
+        proof: Vec<(String, u8)>,
+    ) {
+        self.buy_sell_order_with_condition(
+            sender,
+            collection,
+            token_id,
+            amount,
+            additional_recipient,
+            proof,
+            true,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_sell_order_with_condition(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: U256,
+        amount: U256,
+        additional_recipient: Option<Address>,
+        proof: Vec<(String, u8)>,
+        success: bool,
+    ) {
+        self.0.call_contract_with_condition(
+            sender,
+            "buy_sell_order",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "amount" => amount,
+                "additional_recipient" => additional_recipient,
+                "proof" => proof,
+            },
+            success,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_sell_order_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: U256,
+        amount: U256,
+        additional_recipient: Option<Address>,
+        proof: Vec<(String, u8)>,
+        time: SystemTime,
+        error: E,
+    ) {
+        self.0.call_contract_with_error(
             sender,
             "buy_sell_order",
             runtime_args! {
                 "collection" => collection,
                 "token_id" => token_id,
+                "amount" => amount,
+                "additional_recipient" => additional_recipient,
+                "proof" => proof,
+            },
+            time,
+            error,
+        )
+    }
+
+    pub fn buy_sell_orders(
+        &self,
+        sender: AccountHash,
+        items: Vec<(String, TokenId, U256)>,
+        pay_token: String,
+        all_or_nothing: bool,
+        amount: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "buy_sell_orders",
+            runtime_args! {
+                "items" => items,
+                "pay_token" => pay_token,
+                "all_or_nothing" => all_or_nothing,
+                "amount" => amount,
+            },
+        )
+    }
+
+    pub fn buy_sell_orders_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        items: Vec<(String, TokenId, U256)>,
+        pay_token: String,
+        all_or_nothing: bool,
+        amount: U256,
+        error: E,
+    ) {
+        self.0.call_contract_with_error(
+            sender,
+            "buy_sell_orders",
+            runtime_args! {
+                "items" => items,
+                "pay_token" => pay_token,
+                "all_or_nothing" => all_or_nothing,
+                "amount" => amount,
+            },
+            SystemTime::now(),
+            error,
+        )
+    }
+
+    pub fn fulfill_signed_order(
+        &self,
+        sender: AccountHash,
+        order: SignedOrder,
+        public_key: PublicKey,
+        signature: String,
+    ) {
+        self.fulfill_signed_order_with_condition(sender, order, public_key, signature, true)
+    }
+
+    pub fn fulfill_signed_order_with_condition(
+        &self,
+        sender: AccountHash,
+        order: SignedOrder,
+        public_key: PublicKey,
+        signature: String,
+        success: bool,
+    ) {
+        self.0.call_contract_with_condition(
+            sender,
+            "fulfill_signed_order",
+            runtime_args! {
+                "collection" => order.collection.to_formatted_string(),
+                "token_id" => order.token_id,
+                "price" => order.price,
+                "pay_token" => order.pay_token.unwrap().to_formatted_string(),
+                "expiry" => order.expiry,
+                "nonce" => order.nonce,
+                "epoch" => order.epoch,
+                "public_key" => public_key,
+                "signature" => signature,
+            },
+            success,
+        )
+    }
+
+    pub fn increment_order_epoch(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "increment_order_epoch", runtime_args! {})
+    }
+
+    pub fn cancel_signed_orders(&self, sender: AccountHash, nonces: Vec<u64>) {
+        self.0.call_contract(
+            sender,
+            "cancel_signed_orders",
+            runtime_args! {
+                "nonces" => nonces,
+            },
+        )
+    }
+
+    pub fn create_bundle_sell_order(
+        &self,
+        sender: AccountHash,
+        items: Vec<(String, TokenId)>,
+        pay_token: Option<String>,
+        price: U256,
+        start_time: Time,
+        end_time: Option<Time>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "create_bundle_sell_order",
+            runtime_args! {
+                "items" => items,
+                "pay_token" => pay_token,
+                "price" => price,
+                "start_time" => start_time,
+                "end_time" => end_time,
+            },
+        )
+    }
+
+    pub fn cancel_bundle_sell_order(&self, sender: AccountHash, id: u64) {
+        self.0.call_contract(
+            sender,
+            "cancel_bundle_sell_order",
+            runtime_args! {
+                "id" => id,
+            },
+        )
+    }
+
+    pub fn buy_bundle_sell_order(&self, sender: AccountHash, id: u64, amount: U256) {
+        self.0.call_contract(
+            sender,
+            "buy_bundle_sell_order",
+            runtime_args! {
+                "id" => id,
+                "amount" => amount,
             },
         )
     }
@@ -86,6 +387,7 @@ impl MarketplaceInstance {
         token_id: TokenId,
         additional_recipient: Option<Address>,
         pay_token: String,
+        end_time: Option<Time>,
         amount: U256,
     ) {
         self.0.call_contract(
@@ -96,11 +398,42 @@ impl MarketplaceInstance {
                 "token_id" => token_id,
                 "additional_recipient" => additional_recipient,
                 "pay_token" => pay_token,
+                "end_time" => end_time,
                 "amount" => amount
             },
         )
     }
 
+    pub fn update_buy_order(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        price: U256,
+    ) {
+        self.update_buy_order_with_condition(sender, collection, token_id, price, true)
+    }
+
+    pub fn update_buy_order_with_condition(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        price: U256,
+        success: bool,
+    ) {
+        self.0.call_contract_with_condition(
+            sender,
+            "update_buy_order",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "price" => price,
+            },
+            success,
+        )
+    }
+
     pub fn cancel_buy_order(&self, sender: AccountHash, collection: String, token_id: TokenId) {
         self.0.call_contract(
             sender,
@@ -112,6 +445,24 @@ impl MarketplaceInstance {
         )
     }
 
+    pub fn reclaim_expired_buy_orders(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_ids: Vec<TokenId>,
+        time: SystemTime,
+    ) {
+        self.0.call_contract_with_time(
+            sender,
+            "reclaim_expired_buy_orders",
+            runtime_args! {
+                "collection" => collection,
+                "token_ids" => token_ids,
+            },
+            time,
+        )
+    }
+
     pub fn accept_buy_order(
         &self,
         sender: AccountHash,
@@ -130,6 +481,314 @@ impl MarketplaceInstance {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_collection_offer(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        pay_token: String,
+        price: U256,
+        quantity: u32,
+        end_time: Option<Time>,
+        merkle_root: Option<String>,
+    ) {
+        self.create_collection_offer_with_condition(
+            sender,
+            collection,
+            pay_token,
+            price,
+            quantity,
+            end_time,
+            merkle_root,
+            true,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_collection_offer_with_condition(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        pay_token: String,
+        price: U256,
+        quantity: u32,
+        end_time: Option<Time>,
+        merkle_root: Option<String>,
+        success: bool,
+    ) {
+        self.0.call_contract_with_condition(
+            sender,
+            "create_collection_offer",
+            runtime_args! {
+                "collection" => collection,
+                "pay_token" => pay_token,
+                "price" => price,
+                "quantity" => quantity,
+                "end_time" => end_time,
+                "merkle_root" => merkle_root
+            },
+            success,
+        )
+    }
+
+    pub fn cancel_collection_offer(&self, sender: AccountHash, collection: String) {
+        self.0.call_contract(
+            sender,
+            "cancel_collection_offer",
+            runtime_args! {
+                "collection" => collection
+            },
+        )
+    }
+
+    pub fn accept_collection_offer(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        bidder: Address,
+        proof: Vec<(String, u8)>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "accept_collection_offer",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "bidder" => bidder,
+                "proof" => proof
+            },
+        )
+    }
+
+    pub fn accept_collection_offer_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        bidder: Address,
+        proof: Vec<(String, u8)>,
+        error: E,
+    ) {
+        self.0.call_contract_with_error(
+            sender,
+            "accept_collection_offer",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "bidder" => bidder,
+                "proof" => proof
+            },
+            SystemTime::now(),
+            error,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_auction(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        pay_token: Option<String>,
+        reserve_price: U256,
+        min_bid_increment: U256,
+        start_time: Time,
+        end_time: Time,
+        extension_window: Time,
+    ) {
+        self.0.call_contract(
+            sender,
+            "create_auction",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "pay_token" => pay_token,
+                "reserve_price" => reserve_price,
+                "min_bid_increment" => min_bid_increment,
+                "start_time" => start_time,
+                "end_time" => end_time,
+                "extension_window" => extension_window
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_auction_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        pay_token: Option<String>,
+        reserve_price: U256,
+        min_bid_increment: U256,
+        start_time: Time,
+        end_time: Time,
+        extension_window: Time,
+        error: E,
+    ) {
+        self.0.call_contract_with_error(
+            sender,
+            "create_auction",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "pay_token" => pay_token,
+                "reserve_price" => reserve_price,
+                "min_bid_increment" => min_bid_increment,
+                "start_time" => start_time,
+                "end_time" => end_time,
+                "extension_window" => extension_window
+            },
+            SystemTime::now(),
+            error,
+        )
+    }
+
+    pub fn place_bid(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        amount: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "place_bid",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+                "amount" => amount
+            },
+        )
+    }
+
+    pub fn cancel_auction(&self, sender: AccountHash, collection: String, token_id: TokenId) {
+        self.0.call_contract(
+            sender,
+            "cancel_auction",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+            },
+        )
+    }
+
+    pub fn settle_auction(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        token_id: TokenId,
+        time: SystemTime,
+    ) {
+        self.0.call_contract_with_time(
+            sender,
+            "settle_auction",
+            runtime_args! {
+                "collection" => collection,
+                "token_id" => token_id,
+            },
+            time,
+        )
+    }
+
+    pub fn set_max_auction_extension(&self, sender: AccountHash, window: Time) {
+        self.0.call_contract(
+            sender,
+            "set_max_auction_extension",
+            runtime_args! {
+                "window" => window
+            },
+        )
+    }
+
+    pub fn set_collection_admin(&self, sender: AccountHash, collection: String, admin: Address) {
+        self.0.call_contract(
+            sender,
+            "set_collection_admin",
+            runtime_args! {
+                "collection" => collection,
+                "admin" => admin
+            },
+        )
+    }
+
+    pub fn set_collection_standard(&self, sender: AccountHash, collection: String, standard: u8) {
+        self.0.call_contract(
+            sender,
+            "set_collection_standard",
+            runtime_args! {
+                "collection" => collection,
+                "standard" => standard
+            },
+        )
+    }
+
+    pub fn set_royalty(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        shares: Vec<(Address, u32)>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_royalty",
+            runtime_args! {
+                "collection" => collection,
+                "shares" => shares
+            },
+        )
+    }
+
+    pub fn set_royalty_info_supported(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        supported: bool,
+    ) {
+        self.set_royalty_info_supported_with_condition(sender, collection, supported, true)
+    }
+
+    pub fn set_royalty_info_supported_with_condition(
+        &self,
+        sender: AccountHash,
+        collection: String,
+        supported: bool,
+        success: bool,
+    ) {
+        self.0.call_contract_with_condition(
+            sender,
+            "set_royalty_info_supported",
+            runtime_args! {
+                "collection" => collection,
+                "supported" => supported
+            },
+            success,
+        )
+    }
+
+    pub fn set_acceptable_token(&self, sender: AccountHash, contract_hash: String, fee: u32) {
+        self.0.call_contract(
+            sender,
+            "set_acceptable_token",
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "fee" => fee
+            },
+        )
+    }
+
+    pub fn set_token_standard(&self, sender: AccountHash, contract_hash: String, standard: u8) {
+        self.0.call_contract(
+            sender,
+            "set_token_standard",
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "standard" => standard
+            },
+        )
+    }
+
     pub fn set_fee_wallet(&self, sender: AccountHash, fee_wallet: Key) {
         self.0.call_contract(
             sender,
@@ -148,6 +807,19 @@ impl MarketplaceInstance {
         self.0.query_named_key("fee_wallet".to_string())
     }
 
+    pub fn purse_balance(&self) -> U512 {
+        self.0.query_named_key("purse_balance".to_string())
+    }
+
+    pub fn royalty_info_supported(&self, collection: ContractHash) -> bool {
+        self.0
+            .query_dictionary(
+                "royalty_info_collections",
+                key_to_str(&Key::from(collection)),
+            )
+            .unwrap_or_default()
+    }
+
     pub fn buy_order_of(&self, collection: ContractHash, token_id: U256) -> Bids {
         self.0
             .query_dictionary(
@@ -158,14 +830,60 @@ impl MarketplaceInstance {
     }
 
     pub fn sell_order_of(&self, collection: ContractHash, token_id: U256) -> SellOrder {
+        let id: u64 = self
+            .0
+            .query_dictionary(
+                "active_sell_orders",
+                key_and_value_to_str(&Key::from(collection), &token_id),
+            )
+            .unwrap();
+        self.0
+            .query_dictionary("sell_orders_by_id", id.to_string())
+            .unwrap()
+    }
+
+    pub fn sell_order_by_id(&self, id: u64) -> SellOrder {
+        self.0
+            .query_dictionary("sell_orders_by_id", id.to_string())
+            .unwrap()
+    }
+
+    pub fn auction_of(&self, collection: ContractHash, token_id: U256) -> Auction {
         self.0
             .query_dictionary(
-                "sell_orders",
+                "auctions",
                 key_and_value_to_str(&Key::from(collection), &token_id),
             )
             .unwrap()
     }
 
+    pub fn closed_auction(&self, id: u64) -> Auction {
+        self.0
+            .query_dictionary("closed_auctions", id.to_string())
+            .unwrap()
+    }
+
+    pub fn bundle_of(&self, id: u64) -> Bundle {
+        self.0.query_dictionary("bundles", id.to_string()).unwrap()
+    }
+
+    pub fn collection_offer_of(
+        &self,
+        collection: ContractHash,
+        bidder: Address,
+    ) -> Option<CollectionOffer> {
+        self.0.query_dictionary(
+            "collection_offers",
+            key_and_value_to_str(&Key::from(collection), &bidder),
+        )
+    }
+
+    pub fn closed_collection_offer(&self, id: u64) -> CollectionOffer {
+        self.0
+            .query_dictionary("closed_collection_offers", id.to_string())
+            .unwrap()
+    }
+
     pub fn contract_package_hash(&self) -> ContractPackageHash {
         self.0.contract_package_hash()
     }
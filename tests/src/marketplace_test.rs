@@ -1,17 +1,37 @@
 use casper_types::{
-    account::AccountHash, runtime_args, ContractHash, Key, RuntimeArgs, U256, U512,
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    crypto, runtime_args, ApiError, CLTyped, ContractHash, Key, PublicKey, RuntimeArgs, SecretKey,
+    U256, U512,
+};
+use kunftmarketplace_contract::{
+    Address, Bids, BuyOrder, Error, OrderStatus, SellOrder, SignedOrder, Token,
+};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+    vec,
 };
-use kunftmarketplace_contract::Address;
-use std::{collections::BTreeMap, path::PathBuf, vec};
 use test_env::{utils::DeploySource, TestEnv};
+use tiny_keccak::{Hasher, Keccak};
 
 use crate::{
+    cep18_instance::CEP18Instance,
     cep47_instance::{CEP47Instance, Meta, TokenId},
+    cep78_instance::CEP78Instance,
     erc20_instance::ERC20Instance,
     marketplace_instance::MarketplaceInstance,
 };
 
 const PER_BUY_SELL_ORDER_CSPR_WASM: &str = "pre_buy_sell_order_cspr.wasm";
+const PRE_BUY_SELL_ORDERS_CSPR_WASM: &str = "pre_buy_sell_orders_cspr.wasm";
+const GETTER_SESSION_WASM: &str = "getter_session.wasm";
+const PRE_COLLECTION_OFFER_CSPR_WASM: &str = "pre_collection_offer_cspr.wasm";
+const PRE_BUY_BUNDLE_SELL_ORDER_CSPR_WASM: &str = "pre_buy_bundle_sell_order_cspr.wasm";
+const PRE_ORDER_CSPR_WASM: &str = "pre_order_cspr.wasm";
+const PRE_UPDATE_BUY_ORDER_CSPR_WASM: &str = "pre_update_buy_order_cspr.wasm";
+const PRE_FULFILL_SIGNED_ORDER_CSPR_WASM: &str = "pre_fulfill_signed_order_cspr.wasm";
 
 struct TestContext {
     marketplace: MarketplaceInstance,
@@ -89,6 +109,68 @@ fn deploy() -> (TestEnv, TestContext, AccountHash) {
     (env, test_context, owner)
 }
 
+// test accounts are derived from the ed25519 secret keys `[i; 32]`
+fn signing_keys(account: AccountHash) -> (SecretKey, PublicKey) {
+    (0..10u8)
+        .map(|i| {
+            let secret_key = SecretKey::ed25519_from_bytes([i; 32]).unwrap();
+            let public_key = PublicKey::from(&secret_key);
+            (secret_key, public_key)
+        })
+        .find(|(_, public_key)| AccountHash::from(public_key) == account)
+        .unwrap()
+}
+
+// `signer`'s off-chain USDT listing of `token_id`, returned with the hex encoded signature
+#[allow(clippy::too_many_arguments)]
+fn sign_order(
+    marketplace: &MarketplaceInstance,
+    signer: AccountHash,
+    nft: &CEP47Instance,
+    usdt: &ERC20Instance,
+    token_id: TokenId,
+    price: U256,
+    nonce: u64,
+    epoch: u64,
+) -> (SignedOrder, PublicKey, String) {
+    let order = SignedOrder {
+        collection: nft.contract_hash(),
+        token_id,
+        price,
+        pay_token: Some(usdt.contract_hash()),
+        expiry: signed_order_expiry(),
+        nonce,
+        epoch,
+    };
+    let (secret_key, _) = signing_keys(signer);
+    let (public_key, signature) = sign(marketplace, &secret_key, &order);
+    (order, public_key, signature)
+}
+
+// signs `order` with `secret_key`, returning the public key and the hex encoded signature
+fn sign(
+    marketplace: &MarketplaceInstance,
+    secret_key: &SecretKey,
+    order: &SignedOrder,
+) -> (PublicKey, String) {
+    let public_key = PublicKey::from(secret_key);
+    let signature = crypto::sign(
+        order.message(marketplace.contract_package_hash()),
+        secret_key,
+        &public_key,
+    );
+    (public_key, hex::encode(signature.to_bytes().unwrap()))
+}
+
+// an hour from now, the expiry of every signed order in the tests
+fn signed_order_expiry() -> u64 {
+    unix_time(
+        SystemTime::now()
+            .checked_add(Duration::from_secs(3600))
+            .unwrap(),
+    )
+}
+
 #[test]
 fn should_deploy() {
     let _ = deploy();
@@ -121,9 +203,13 @@ fn should_create_sell_order_and_buy_cspr() {
     marketplace.create_sell_order(
         user,
         0u64,
+        None,
         nft.contract_hash().to_formatted_string(),
         tokens,
         pay_token,
+        None,
+        None,
+        true,
     );
 
     // buy token 0
@@ -139,7 +225,8 @@ fn should_create_sell_order_and_buy_cspr() {
             "collection" => nft.contract_hash().to_formatted_string(),
             "token_id" => token_0,
             "amount" => price_u512_0,
-            "additional_recipient" => additional_recipient
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
         },
     );
 
@@ -159,7 +246,8 @@ fn should_create_sell_order_and_buy_cspr() {
             "collection" => nft.contract_hash().to_formatted_string(),
             "token_id" => token_1,
             "amount" => price_u512_1,
-            "additional_recipient" => additional_recipient
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
         },
     );
 
@@ -167,40 +255,249 @@ fn should_create_sell_order_and_buy_cspr() {
     assert_eq!(nft_owner, additional_recipient.unwrap());
 }
 
+// lists tokens 0 and 1 of `user` for 50 and 100 CSPR
+fn list_sweepable_tokens(test_context: &TestContext, owner: AccountHash, user: AccountHash) {
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let token_0 = TokenId::zero();
+    let token_1 = TokenId::one();
+    nft.mint_copies(owner, user, vec![token_0, token_1], meta::red_dragon(), 2);
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_0, token_1],
+    );
+
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(
+        token_0,
+        U256::from(50u8).checked_mul(U256::exp10(9)).unwrap(),
+    );
+    tokens.insert(
+        token_1,
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+    );
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        None,
+        None,
+        None,
+        true,
+    );
+}
+
+// token 1 is above its max price and token 2 is not listed
+fn sweep_items(test_context: &TestContext) -> Vec<(String, TokenId, U256)> {
+    let collection = test_context.nft.contract_hash().to_formatted_string();
+    vec![
+        (
+            collection.clone(),
+            TokenId::zero(),
+            U256::from(50u8).checked_mul(U256::exp10(9)).unwrap(),
+        ),
+        (
+            collection.clone(),
+            TokenId::one(),
+            U256::from(80u8).checked_mul(U256::exp10(9)).unwrap(),
+        ),
+        (
+            collection,
+            U256::from(2u8),
+            U256::from(50u8).checked_mul(U256::exp10(9)).unwrap(),
+        ),
+    ]
+}
+
 #[test]
-fn should_create_sell_order_and_buy() {
+fn should_sweep_sell_orders_cspr_best_effort() {
     let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    list_sweepable_tokens(&test_context, owner, user);
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+
+    // the unavailable orders are skipped
+    let buyer = env.next_user();
+    let seller_balance = env.balance_of(user);
+    env.run(
+        buyer,
+        DeploySource::Code(PathBuf::from(PRE_BUY_SELL_ORDERS_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "items" => sweep_items(&test_context),
+            "all_or_nothing" => false,
+            "amount" => U512::from(200u8).checked_mul(U512::exp10(9)).unwrap()
+        },
+    );
+
+    assert_eq!(nft.owner_of(TokenId::zero()).unwrap(), Key::from(buyer));
+    assert_eq!(
+        nft.owner_of(TokenId::one()).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+    // only token 0 is paid, minus the 10% fee
+    assert_eq!(
+        env.balance_of(user) - seller_balance,
+        U512::from(45u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    // the unspent 150 CSPR are refunded
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+}
 
+#[test]
+fn should_not_sweep_sell_orders_cspr_all_or_nothing() {
+    let (env, test_context, owner) = deploy();
     let user = env.next_user();
-    let token_id = TokenId::zero();
-    let token_meta = meta::red_dragon();
-    let nft = test_context.nft;
-    let marketplace = test_context.marketplace;
-    nft.mint_one(owner, user, token_id, token_meta);
+    list_sweepable_tokens(&test_context, owner, user);
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
 
-    nft.approve(
-        user,
-        Key::from(marketplace.contract_package_hash()),
-        vec![token_id],
+    // UnavailableOrder
+    let buyer = env.next_user();
+    let seller_balance = env.balance_of(user);
+    env.run_with_condition(
+        buyer,
+        DeploySource::Code(PathBuf::from(PRE_BUY_SELL_ORDERS_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "items" => sweep_items(&test_context),
+            "all_or_nothing" => true,
+            "amount" => U512::from(200u8).checked_mul(U512::exp10(9)).unwrap()
+        },
+        false,
     );
 
-    // Mint ERC20
+    assert_eq!(
+        nft.owner_of(TokenId::zero()).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+    assert_eq!(
+        nft.owner_of(TokenId::one()).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+    assert_eq!(env.balance_of(user), seller_balance);
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+}
 
-    let pay_token: Option<String> = None;
-    let price = U256::one();
+// lists tokens 0 and 1 of `seller` for 50 and 100 USDT without escrow, then moves token 1 to
+// `holder` so that its order is stale. Returns the sweep items covering both orders.
+fn list_stale_usdt_tokens(
+    test_context: &TestContext,
+    owner: AccountHash,
+    seller: AccountHash,
+    holder: AccountHash,
+) -> Vec<(String, TokenId, U256)> {
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let token_0 = TokenId::zero();
+    let token_1 = TokenId::one();
+    let price_0 = U256::from(50u8).checked_mul(U256::exp10(9)).unwrap();
+    let price_1 = U256::from(100u8).checked_mul(U256::exp10(9)).unwrap();
+    nft.mint_copies(owner, seller, vec![token_0, token_1], meta::red_dragon(), 2);
+    nft.approve(
+        seller,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_0, token_1],
+    );
     let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
-    tokens.insert(token_id, price);
+    tokens.insert(token_0, price_0);
+    tokens.insert(token_1, price_1);
     marketplace.create_sell_order(
-        user,
+        seller,
         0u64,
+        None,
         nft.contract_hash().to_formatted_string(),
         tokens,
-        pay_token,
+        Some(test_context.erc20.contract_hash().to_formatted_string()),
+        None,
+        None,
+        false,
     );
+    nft.transfer(seller, holder, vec![token_1]);
+
+    let collection = nft.contract_hash().to_formatted_string();
+    vec![
+        (collection.clone(), token_0, price_0),
+        (collection, token_1, price_1),
+    ]
 }
 
 #[test]
-fn should_create_sell_order_and_cancel() {
+fn should_not_sweep_sell_orders_all_or_nothing_with_stale_order() {
+    let (env, test_context, owner) = deploy();
+    let ali = env.next_user();
+    let carol = env.next_user();
+    let items = list_stale_usdt_tokens(&test_context, owner, ali, carol);
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let usdt = &test_context.erc20;
+
+    let bob = env.next_user();
+    let amount = U256::from(150u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), amount);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), amount);
+    marketplace.buy_sell_orders_with_error(
+        bob,
+        items,
+        usdt.contract_hash().to_formatted_string(),
+        true,
+        amount,
+        Error::UnavailableOrder,
+    );
+
+    // nothing is filled, not even the available order
+    assert_eq!(nft.owner_of(TokenId::zero()).unwrap(), Key::from(ali));
+    assert_eq!(nft.owner_of(TokenId::one()).unwrap(), Key::from(carol));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), amount);
+    assert_eq!(
+        marketplace
+            .sell_order_of(nft.contract_hash(), TokenId::zero())
+            .status,
+        OrderStatus::Pending
+    );
+}
+
+#[test]
+fn should_sweep_sell_orders_best_effort_skipping_stale_order() {
+    let (env, test_context, owner) = deploy();
+    let ali = env.next_user();
+    let carol = env.next_user();
+    let items = list_stale_usdt_tokens(&test_context, owner, ali, carol);
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let usdt = &test_context.erc20;
+
+    let bob = env.next_user();
+    let amount = U256::from(150u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), amount);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), amount);
+    marketplace.buy_sell_orders(
+        bob,
+        items,
+        usdt.contract_hash().to_formatted_string(),
+        false,
+        amount,
+    );
+
+    assert_eq!(nft.owner_of(TokenId::zero()).unwrap(), Key::from(bob));
+    assert_eq!(nft.owner_of(TokenId::one()).unwrap(), Key::from(carol));
+    // only token 0 is paid, minus the 5% fee
+    assert_eq!(
+        usdt.balance_of(Key::from(ali)).unwrap(),
+        U256::from(475u16).checked_mul(U256::exp10(8)).unwrap()
+    );
+    assert_eq!(
+        usdt.balance_of(Key::from(bob)).unwrap(),
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap()
+    );
+}
+
+#[test]
+fn should_buy_dutch_sell_order_at_declined_price() {
     let (env, test_context, owner) = deploy();
     let user = env.next_user();
     let token_id = TokenId::zero();
@@ -208,71 +505,240 @@ fn should_create_sell_order_and_cancel() {
     let nft = test_context.nft;
     let marketplace = test_context.marketplace;
     nft.mint_one(owner, user, token_id, token_meta);
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let now = SystemTime::now();
+    let start_time = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let duration = 3600u64;
+    let start_price = U256::from(100u8).checked_mul(U256::exp10(9)).unwrap();
+    let end_price = U256::from(50u8).checked_mul(U256::exp10(9)).unwrap();
+    marketplace.create_dutch_sell_order(
+        user,
+        start_time,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        start_price,
+        end_price,
+        duration,
+    );
+
+    // once the duration has elapsed the buyer only pays the end price, the rest of the
+    // deposit is refunded
+    let buyer = env.next_user();
+    let seller_balance = env.balance_of(user);
+    let fee_wallet_balance = env.balance_of(owner);
+    let session_code = PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM);
+    let additional_recipient: Option<Address> = None;
+    env.run_with_time(
+        buyer,
+        DeploySource::Code(session_code),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "token_id" => token_id,
+            "amount" => U512::from(100u8).checked_mul(U512::exp10(9)).unwrap(),
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
+        },
+        now.checked_add(Duration::from_secs(duration)).unwrap(),
+    );
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(buyer));
+    // 10% CSPR fee on the end price
+    assert_eq!(
+        env.balance_of(user) - seller_balance,
+        U512::from(45u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(
+        env.balance_of(owner) - fee_wallet_balance,
+        U512::from(5u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(data);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+// keccak256 merkle tree over four leaves, returns the root and the proof of every leaf
+fn merkle_tree_of_four(leaves: [String; 4]) -> ([u8; 32], Vec<Vec<(String, u8)>>) {
+    let hashes: Vec<[u8; 32]> = leaves
+        .iter()
+        .map(|leaf| keccak256(leaf.as_bytes()))
+        .collect();
+    let left = keccak256(&[hashes[0], hashes[1]].concat());
+    let right = keccak256(&[hashes[2], hashes[3]].concat());
+    let root = keccak256(&[left, right].concat());
+    // position 1 puts the proof item on the right of the computed hash, 0 on the left
+    let proofs = (0..4)
+        .map(|index| {
+            vec![
+                (hex::encode(hashes[index ^ 1]), (index % 2 == 0) as u8),
+                (
+                    hex::encode(if index < 2 { right } else { left }),
+                    (index < 2) as u8,
+                ),
+            ]
+        })
+        .collect();
+    (root, proofs)
+}
 
+// lists token 0 of `user` for 50 CSPR to the allowlisted `buyers`
+fn list_allowlisted_token(
+    test_context: &TestContext,
+    owner: AccountHash,
+    user: AccountHash,
+    buyers: [AccountHash; 4],
+) -> Vec<Vec<(String, u8)>> {
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, user, token_id, meta::red_dragon());
     nft.approve(
         user,
         Key::from(marketplace.contract_package_hash()),
         vec![token_id],
     );
 
-    let pay_token: Option<String> = None;
-    let price = U256::one();
+    let (allowlist_root, proofs) =
+        merkle_tree_of_four(buyers.map(|buyer| Key::from(buyer).to_formatted_string()));
+    let price = U256::from(50u8).checked_mul(U256::exp10(9)).unwrap();
     let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
     tokens.insert(token_id, price);
     marketplace.create_sell_order(
         user,
         0u64,
+        None,
         nft.contract_hash().to_formatted_string(),
         tokens,
-        pay_token,
+        None,
+        Some(hex::encode(allowlist_root)),
+        None,
+        true,
     );
+    proofs
+}
 
-    marketplace.cancel_sell_order(
-        user,
-        nft.contract_hash().to_formatted_string(),
-        vec![token_id],
+fn buy_allowlisted_token(
+    env: &TestEnv,
+    test_context: &TestContext,
+    buyer: AccountHash,
+    proof: Vec<(String, u8)>,
+    success: bool,
+) {
+    let additional_recipient: Option<Address> = None;
+    env.run_with_condition(
+        buyer,
+        DeploySource::Code(PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => test_context.marketplace.contract_hash().to_formatted_string(),
+            "collection" => test_context.nft.contract_hash().to_formatted_string(),
+            "token_id" => TokenId::zero(),
+            "amount" => U512::from(50u8).checked_mul(U512::exp10(9)).unwrap(),
+            "additional_recipient" => additional_recipient,
+            "proof" => proof
+        },
+        success,
     );
 }
 
 #[test]
-fn should_create_buy_order_and_cancel() {
+fn should_buy_allowlisted_sell_order_cspr() {
     let (env, test_context, owner) = deploy();
-    let marketplace = test_context.marketplace;
-    let nft = test_context.nft;
-    let usdt = test_context.erc20;
+    let user = env.next_user();
+    let buyers = [
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+    ];
+    let mut proofs = list_allowlisted_token(&test_context, owner, user, buyers);
 
-    let ali = env.next_user();
-    let token_id = TokenId::zero();
-    let token_meta = meta::red_dragon();
-    nft.mint_one(owner, ali, token_id, token_meta);
+    buy_allowlisted_token(&env, &test_context, buyers[2], proofs.remove(2), true);
 
-    let bob = env.next_user();
-    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
-    usdt.transfer(owner, Key::from(bob), offer_amount);
-    usdt.approve(
-        bob,
-        Key::from(marketplace.contract_package_hash()),
-        offer_amount,
+    assert_eq!(
+        test_context.nft.owner_of(TokenId::zero()).unwrap(),
+        Key::from(buyers[2])
     );
-    let mut bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
-    assert_eq!(bob_usdt_balance, offer_amount);
+}
 
-    marketplace.create_buy_order(
-        bob,
-        nft.contract_hash().to_formatted_string(),
-        token_id,
-        None,
-        usdt.contract_hash().to_formatted_string(),
-        offer_amount,
+#[test]
+fn should_not_buy_allowlisted_sell_order_with_wrong_proof() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let buyers = [
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+    ];
+    let proofs = list_allowlisted_token(&test_context, owner, user, buyers);
+
+    // the proof of another leaf
+    buy_allowlisted_token(&env, &test_context, buyers[2], proofs[1].clone(), false);
+    // an account outside of the allowlist
+    let stranger = env.next_user();
+    buy_allowlisted_token(&env, &test_context, stranger, proofs[2].clone(), false);
+
+    assert_eq!(
+        test_context.nft.owner_of(TokenId::zero()).unwrap(),
+        Key::from(test_context.marketplace.contract_package_hash())
     );
-    bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
-    assert_eq!(bob_usdt_balance, U256::zero());
+}
 
-    marketplace.cancel_buy_order(bob, nft.contract_hash().to_formatted_string(), token_id);
+#[test]
+fn should_revert_allowlisted_purchase_with_malformed_proof() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let buyers = [
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+        env.next_user(),
+    ];
+    let proofs = list_allowlisted_token(&test_context, owner, user, buyers);
+
+    let mut unknown_position = proofs[2].clone();
+    unknown_position[0].1 = 2;
+    let mut not_hex = proofs[2].clone();
+    not_hex[0].0 = "not a hash".to_string();
+    for proof in [unknown_position, not_hex] {
+        let additional_recipient: Option<Address> = None;
+        env.run_with_error(
+            buyers[2],
+            DeploySource::Code(PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM)),
+            runtime_args! {
+                "marketplace_contract" => test_context.marketplace.contract_hash().to_formatted_string(),
+                "collection" => test_context.nft.contract_hash().to_formatted_string(),
+                "token_id" => TokenId::zero(),
+                "amount" => U512::from(50u8).checked_mul(U512::exp10(9)).unwrap(),
+                "additional_recipient" => additional_recipient,
+                "proof" => proof
+            },
+            SystemTime::now(),
+            Error::InvalidProof,
+        );
+    }
+
+    assert_eq!(
+        test_context.nft.owner_of(TokenId::zero()).unwrap(),
+        Key::from(test_context.marketplace.contract_package_hash())
+    );
+    assert_eq!(test_context.marketplace.purse_balance(), U512::zero());
 }
 
 #[test]
-fn should_create_buy_order_and_accept() {
+fn should_buy_reserved_sell_order() {
     let (env, test_context, owner) = deploy();
     let marketplace = test_context.marketplace;
     let nft = test_context.nft;
@@ -280,43 +746,2531 @@ fn should_create_buy_order_and_accept() {
 
     let ali = env.next_user();
     let token_id = TokenId::zero();
-    let token_meta = meta::red_dragon();
-    nft.mint_one(owner, ali, token_id, token_meta);
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
 
     let bob = env.next_user();
-    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
-    usdt.transfer(owner, Key::from(bob), offer_amount);
-    usdt.approve(
-        bob,
-        Key::from(marketplace.contract_package_hash()),
-        offer_amount,
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(usdt.contract_hash().to_formatted_string()),
+        None,
+        Some(Address::from(bob)),
+        true,
     );
-    let mut bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
-    assert_eq!(bob_usdt_balance, offer_amount);
 
-    marketplace.create_buy_order(
+    // NotReservedBuyer
+    let carol = env.next_user();
+    usdt.transfer(owner, Key::from(carol), price);
+    usdt.approve(carol, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order_with_condition(
+        carol,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+        false,
+    );
+    assert_eq!(usdt.balance_of(Key::from(carol)).unwrap(), price);
+    assert_eq!(
+        nft.owner_of(token_id).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order(
         bob,
         nft.contract_hash().to_formatted_string(),
         token_id,
+        price,
         None,
-        usdt.contract_hash().to_formatted_string(),
-        offer_amount,
+        vec![],
     );
-    bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
-    assert_eq!(bob_usdt_balance, U256::zero());
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+}
+
+#[test]
+fn should_create_sell_order_and_buy() {
+    let (env, test_context, owner) = deploy();
+
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    let nft = test_context.nft;
+    let marketplace = test_context.marketplace;
+    nft.mint_one(owner, user, token_id, token_meta);
+
     nft.approve(
-        ali,
+        user,
         Key::from(marketplace.contract_package_hash()),
         vec![token_id],
     );
-    marketplace.accept_buy_order(
-        ali,
-        nft.contract_hash().to_formatted_string(),
+
+    // Mint ERC20
+
+    let pay_token: Option<String> = None;
+    let price = U256::one();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        pay_token,
+        None,
+        None,
+        true,
+    );
+}
+
+#[test]
+fn should_buy_sell_order_of_cep78_collection() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let marketplace = test_context.marketplace;
+    let nft = CEP78Instance::new(&env, "cep78", owner);
+    let collection = nft.contract_hash().to_formatted_string();
+    // CEP78Ordinal
+    marketplace.set_collection_standard(owner, collection.clone(), 1);
+
+    let token_id = 7u64;
+    nft.mint(owner, user, token_id);
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        token_id,
+    );
+
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(
+        TokenId::from(token_id),
+        U256::from(50u8).checked_mul(U256::exp10(9)).unwrap(),
+    );
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        None,
+        collection.clone(),
+        tokens,
+        None,
+        None,
+        None,
+        true,
+    );
+    assert_eq!(
+        nft.owner_of(token_id).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+
+    let buyer = env.next_user();
+    let session_code = PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM);
+    let additional_recipient: Option<Address> = None;
+    env.run(
+        buyer,
+        DeploySource::Code(session_code),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => collection,
+            "token_id" => TokenId::from(token_id),
+            "amount" => U512::from(50u8).checked_mul(U512::exp10(9)).unwrap(),
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
+        },
+    );
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(buyer));
+}
+
+#[test]
+fn should_buy_bundle_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_0 = TokenId::zero();
+    let token_1 = TokenId::one();
+    nft.mint_copies(owner, ali, vec![token_0, token_1], meta::red_dragon(), 2);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_0, token_1],
+    );
+
+    let price = U256::from(100u8).checked_mul(U256::exp10(9)).unwrap();
+    let collection = nft.contract_hash().to_formatted_string();
+    marketplace.create_bundle_sell_order(
+        ali,
+        vec![(collection.clone(), token_0), (collection, token_1)],
+        Some(usdt.contract_hash().to_formatted_string()),
+        price,
+        0u64,
+        None,
+    );
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    // the bundle is the first order
+    marketplace.buy_bundle_sell_order(bob, 1u64, price);
+
+    assert_eq!(nft.owner_of(token_0).unwrap(), Key::from(bob));
+    assert_eq!(nft.owner_of(token_1).unwrap(), Key::from(bob));
+    // 5% USDT fee
+    assert_eq!(
+        usdt.balance_of(Key::from(ali)).unwrap(),
+        U256::from(95u8).checked_mul(U256::exp10(9)).unwrap()
+    );
+    assert_eq!(marketplace.bundle_of(1u64).status, OrderStatus::Completed);
+}
+
+#[test]
+fn should_buy_bundle_sell_order_cspr_and_refund_overpayment() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_0 = TokenId::zero();
+    let token_1 = TokenId::one();
+    nft.mint_copies(owner, ali, vec![token_0, token_1], meta::red_dragon(), 2);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_0, token_1],
+    );
+
+    let collection = nft.contract_hash().to_formatted_string();
+    marketplace.create_bundle_sell_order(
+        ali,
+        vec![(collection.clone(), token_0), (collection, token_1)],
+        None,
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+        0u64,
+        None,
+    );
+
+    let bob = env.next_user();
+    let bundle_args = |amount: U512| {
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "id" => 1u64,
+            "amount" => amount
+        }
+    };
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_BUY_BUNDLE_SELL_ORDER_CSPR_WASM)),
+        bundle_args(U512::from(99u8).checked_mul(U512::exp10(9)).unwrap()),
+        SystemTime::now(),
+        Error::InsufficientBalance,
+    );
+
+    let seller_balance = env.balance_of(ali);
+    let fee_wallet_balance = env.balance_of(owner);
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_BUY_BUNDLE_SELL_ORDER_CSPR_WASM)),
+        bundle_args(U512::from(120u8).checked_mul(U512::exp10(9)).unwrap()),
+    );
+
+    assert_eq!(nft.owner_of(token_0).unwrap(), Key::from(bob));
+    assert_eq!(nft.owner_of(token_1).unwrap(), Key::from(bob));
+    // 10% CSPR fee of the 100 CSPR price
+    assert_eq!(
+        env.balance_of(ali) - seller_balance,
+        U512::from(90u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(
+        env.balance_of(owner) - fee_wallet_balance,
+        U512::from(10u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    // the 20 CSPR overpayment is refunded
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+    assert_eq!(marketplace.bundle_of(1u64).status, OrderStatus::Completed);
+}
+
+#[test]
+fn should_cancel_bundle_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_0 = TokenId::zero();
+    let token_1 = TokenId::one();
+    nft.mint_copies(owner, ali, vec![token_0, token_1], meta::red_dragon(), 2);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_0, token_1],
+    );
+
+    let collection = nft.contract_hash().to_formatted_string();
+    marketplace.create_bundle_sell_order(
+        ali,
+        vec![(collection.clone(), token_0), (collection, token_1)],
+        None,
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+        0u64,
+        None,
+    );
+    marketplace.cancel_bundle_sell_order(ali, 1u64);
+
+    // the items are returned and the record is kept
+    assert_eq!(nft.owner_of(token_0).unwrap(), Key::from(ali));
+    assert_eq!(nft.owner_of(token_1).unwrap(), Key::from(ali));
+    assert_eq!(marketplace.bundle_of(1u64).status, OrderStatus::Canceled);
+}
+
+#[test]
+fn should_buy_non_custodial_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(usdt.contract_hash().to_formatted_string()),
+        None,
+        None,
+        false,
+    );
+    // the token stays with the seller while listed
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(ali));
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+    );
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+}
+
+#[test]
+fn should_keep_sold_order_when_token_is_relisted() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens.clone(),
+        Some(usdt.contract_hash().to_formatted_string()),
+        None,
+        None,
+        true,
+    );
+    let sold_order_id = marketplace.sell_order_of(nft.contract_hash(), token_id).id;
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+    );
+
+    // bob lists the token again under a new order id
+    nft.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    marketplace.create_sell_order(
+        bob,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(usdt.contract_hash().to_formatted_string()),
+        None,
+        None,
+        true,
+    );
+    let relisted_order = marketplace.sell_order_of(nft.contract_hash(), token_id);
+    assert_ne!(relisted_order.id, sold_order_id);
+    assert_eq!(relisted_order.creator, Address::from(bob));
+
+    let sold_order = marketplace.sell_order_by_id(sold_order_id);
+    assert_eq!(sold_order.creator, Address::from(ali));
+    assert_eq!(sold_order.status, OrderStatus::Completed);
+}
+
+#[test]
+fn should_invalidate_stale_non_custodial_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(usdt.contract_hash().to_formatted_string()),
+        None,
+        None,
+        false,
+    );
+
+    // the seller moves the token elsewhere after listing it
+    let carol = env.next_user();
+    nft.transfer(ali, carol, vec![token_id]);
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+    );
+
+    // the deploy succeeds, the order is invalidated without charging the buyer
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(carol));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+    assert_eq!(
+        marketplace
+            .sell_order_of(nft.contract_hash(), token_id)
+            .status,
+        OrderStatus::Invalidated
+    );
+}
+
+#[test]
+fn should_fulfill_signed_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    // ali signs the listing off-chain
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let (order, public_key, signature) =
+        sign_order(&marketplace, ali, &nft, &usdt, token_id, price, 1, 0);
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.fulfill_signed_order(bob, order, public_key, signature);
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+}
+
+#[test]
+fn should_not_fulfill_signed_order_after_epoch_increment() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    // ali signs the listing off-chain
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let (order, public_key, signature) =
+        sign_order(&marketplace, ali, &nft, &usdt, token_id, price, 1, 0);
+
+    // bumping the epoch voids every outstanding signature of ali
+    marketplace.increment_order_epoch(ali);
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.fulfill_signed_order_with_condition(bob, order, public_key, signature, false);
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(ali));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+
+    // the same listing signed for the new epoch goes through
+    let (order, public_key, signature) =
+        sign_order(&marketplace, ali, &nft, &usdt, token_id, price, 1, 1);
+    marketplace.fulfill_signed_order(bob, order, public_key, signature);
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+}
+
+#[test]
+fn should_not_fulfill_cancelled_signed_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_ids = vec![TokenId::zero(), TokenId::one()];
+    for token_id in token_ids.iter() {
+        nft.mint_one(owner, ali, *token_id, meta::red_dragon());
+    }
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        token_ids.clone(),
+    );
+
+    // ali signs both listings off-chain, then cancels the first one
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let (cancelled_order, cancelled_public_key, cancelled_signature) =
+        sign_order(&marketplace, ali, &nft, &usdt, token_ids[0], price, 1, 0);
+    let (order, public_key, signature) =
+        sign_order(&marketplace, ali, &nft, &usdt, token_ids[1], price, 2, 0);
+    marketplace.cancel_signed_orders(ali, vec![1]);
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.fulfill_signed_order_with_condition(
+        bob,
+        cancelled_order,
+        cancelled_public_key,
+        cancelled_signature,
+        false,
+    );
+    assert_eq!(nft.owner_of(token_ids[0]).unwrap(), Key::from(ali));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+
+    // other nonces of ali are untouched
+    marketplace.fulfill_signed_order(bob, order, public_key, signature);
+    assert_eq!(nft.owner_of(token_ids[1]).unwrap(), Key::from(bob));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+}
+
+#[test]
+fn should_fulfill_signed_order_cspr() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    // ali signs a CSPR listing off-chain
+    let order = SignedOrder {
+        collection: nft.contract_hash(),
+        token_id,
+        price: U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+        pay_token: None,
+        expiry: signed_order_expiry(),
+        nonce: 1,
+        epoch: 0,
+    };
+    let (secret_key, _) = signing_keys(ali);
+    let (public_key, signature) = sign(&marketplace, &secret_key, &order);
+
+    let bob = env.next_user();
+    let fulfill_args = |amount: U512| {
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => order.collection.to_formatted_string(),
+            "token_id" => order.token_id,
+            "price" => order.price,
+            "expiry" => order.expiry,
+            "nonce" => order.nonce,
+            "epoch" => order.epoch,
+            "public_key" => public_key.clone(),
+            "signature" => signature.clone(),
+            "amount" => amount
+        }
+    };
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_FULFILL_SIGNED_ORDER_CSPR_WASM)),
+        fulfill_args(U512::from(99u8).checked_mul(U512::exp10(9)).unwrap()),
+        SystemTime::now(),
+        Error::InsufficientBalance,
+    );
+
+    let seller_balance = env.balance_of(ali);
+    let fee_wallet_balance = env.balance_of(owner);
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_FULFILL_SIGNED_ORDER_CSPR_WASM)),
+        fulfill_args(U512::from(120u8).checked_mul(U512::exp10(9)).unwrap()),
+    );
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    // 10% CSPR fee of the 100 CSPR price
+    assert_eq!(
+        env.balance_of(ali) - seller_balance,
+        U512::from(90u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(
+        env.balance_of(owner) - fee_wallet_balance,
+        U512::from(10u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    // the 20 CSPR overpayment is refunded
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+
+    // the nonce is spent once the order settles
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_FULFILL_SIGNED_ORDER_CSPR_WASM)),
+        fulfill_args(U512::from(100u8).checked_mul(U512::exp10(9)).unwrap()),
+        SystemTime::now(),
+        Error::UsedNonce,
+    );
+}
+
+#[test]
+fn should_fulfill_signed_order_of_secp256k1_key() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let secret_key = SecretKey::secp256k1_from_bytes([1u8; 32]).unwrap();
+    let carol = env.add_user(&PublicKey::from(&secret_key));
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, carol, token_id, meta::red_dragon());
+    nft.approve(
+        carol,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    // carol signs the listing off-chain with a secp256k1 key
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let order = SignedOrder {
+        collection: nft.contract_hash(),
+        token_id,
+        price,
+        pay_token: Some(usdt.contract_hash()),
+        expiry: signed_order_expiry(),
+        nonce: 1,
+        epoch: 0,
+    };
+    let (public_key, signature) = sign(&marketplace, &secret_key, &order);
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.fulfill_signed_order(bob, order, public_key, signature);
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+    // 5% USDT fee of the price
+    assert_eq!(
+        usdt.balance_of(Key::from(carol)).unwrap(),
+        price - price * 5 / 100
+    );
+}
+
+#[test]
+fn should_update_sell_order_price() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    let nft = test_context.nft;
+    let marketplace = test_context.marketplace;
+    nft.mint_one(owner, user, token_id, meta::red_dragon());
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(
+        token_id,
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+    );
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        None,
+        None,
+        None,
+        true,
+    );
+
+    let new_price = U256::from(50u8).checked_mul(U256::exp10(9)).unwrap();
+    marketplace.update_sell_order(
+        user,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        new_price,
+        None,
+        0u64,
+        None,
+    );
+
+    // the listing stays in escrow and sells at the new price
+    let buyer = env.next_user();
+    let additional_recipient: Option<Address> = None;
+    env.run(
+        buyer,
+        DeploySource::Code(PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "token_id" => token_id,
+            "amount" => U512::from(50u8).checked_mul(U512::exp10(9)).unwrap(),
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
+        },
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(buyer));
+}
+
+// Runs `entry_point` of the marketplace from the getter session and reads back what it returned
+fn call_getter<T: CLTyped + FromBytes>(
+    env: &TestEnv,
+    marketplace: &MarketplaceInstance,
+    sender: AccountHash,
+    entry_point: &str,
+    mut args: RuntimeArgs,
+) -> T {
+    args.insert(
+        "marketplace_contract",
+        marketplace.contract_hash().to_formatted_string(),
+    )
+    .unwrap();
+    args.insert("entry_point", entry_point.to_string()).unwrap();
+    env.run(
+        sender,
+        DeploySource::Code(PathBuf::from(GETTER_SESSION_WASM)),
+        args,
+    );
+    env.query_account_named_key(sender, &["result".to_string()])
+}
+
+#[test]
+fn should_call_getters() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let price = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        None,
+        None,
+        None,
+        true,
+    );
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(60u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), offer_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        offer_amount,
+    );
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        offer_amount,
+    );
+
+    let collection = nft.contract_hash().to_formatted_string();
+    let order: SellOrder = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_sell_order",
+        runtime_args! {
+            "collection" => collection.clone(),
+            "token_id" => token_id,
+        },
+    );
+    assert_eq!(order.creator, Address::from(ali));
+    assert_eq!(order.price, price);
+    assert_eq!(order.status, OrderStatus::Pending);
+
+    let order_by_id: SellOrder = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_sell_order_by_id",
+        runtime_args! { "id" => order.id },
+    );
+    assert_eq!(order_by_id.token_id, token_id);
+    assert_eq!(order_by_id.creator, Address::from(ali));
+
+    let bids: Bids = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_buy_orders",
+        runtime_args! {
+            "collection" => collection.clone(),
+            "token_id" => token_id,
+        },
+    );
+    assert_eq!(bids.len(), 1);
+    assert_eq!(bids.get(&Address::from(bob)).unwrap().price, offer_amount);
+
+    let cspr_fee: u32 = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_fee",
+        runtime_args! { "token" => Option::<String>::None },
+    );
+    assert_eq!(cspr_fee, 1000);
+    let usdt_fee: u32 = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_fee",
+        runtime_args! { "token" => Some(usdt.contract_hash().to_formatted_string()) },
+    );
+    assert_eq!(usdt_fee, 500);
+
+    let fee_wallet: Address =
+        call_getter(&env, &marketplace, bob, "get_fee_wallet", runtime_args! {});
+    assert_eq!(fee_wallet, Address::from(owner));
+
+    let cspr_acceptable: bool = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "is_acceptable_token",
+        runtime_args! { "token" => Option::<String>::None },
+    );
+    assert!(cspr_acceptable);
+    let nft_acceptable: bool = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "is_acceptable_token",
+        runtime_args! { "token" => Some(collection.clone()) },
+    );
+    assert!(!nft_acceptable);
+
+    // CSPR is listed as the null contract hash
+    let acceptable_tokens: Vec<ContractHash> = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "list_acceptable_tokens",
+        runtime_args! {},
+    );
+    assert_eq!(acceptable_tokens.len(), 2);
+    assert!(acceptable_tokens.contains(&ContractHash::new([0u8; 32])));
+    assert!(acceptable_tokens.contains(&usdt.contract_hash()));
+
+    let seller_orders: Vec<SellOrder> = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_sell_orders_by_seller",
+        runtime_args! {
+            "seller" => Address::from(ali),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(
+        seller_orders
+            .iter()
+            .map(|order| order.id)
+            .collect::<Vec<u64>>(),
+        vec![order.id]
+    );
+    let collection_orders: Vec<SellOrder> = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_sell_orders_by_collection",
+        runtime_args! {
+            "collection" => collection,
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(
+        collection_orders
+            .iter()
+            .map(|order| order.id)
+            .collect::<Vec<u64>>(),
+        vec![order.id]
+    );
+
+    let bidder_orders: Vec<(Token, BuyOrder)> = call_getter(
+        &env,
+        &marketplace,
+        bob,
+        "get_buy_orders_by_bidder",
+        runtime_args! {
+            "bidder" => Address::from(bob),
+            "offset" => 0u64,
+            "limit" => 10u64,
+        },
+    );
+    assert_eq!(bidder_orders.len(), 1);
+    assert_eq!(bidder_orders[0].0, (nft.contract_hash(), token_id));
+    assert_eq!(bidder_orders[0].1.price, offer_amount);
+}
+
+#[test]
+fn should_page_sell_orders_after_swap_remove() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_ids: Vec<TokenId> = (0..4u8).map(TokenId::from).collect();
+    nft.mint_copies(owner, ali, token_ids.clone(), meta::red_dragon(), 4);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        token_ids.clone(),
+    );
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    token_ids.iter().for_each(|token_id| {
+        tokens.insert(*token_id, U256::exp10(9));
+    });
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        None,
+        None,
+        None,
+        true,
+    );
+    let ids: Vec<u64> = token_ids
+        .iter()
+        .map(|token_id| marketplace.sell_order_of(nft.contract_hash(), *token_id).id)
+        .collect();
+
+    // the last order takes the place of the canceled one
+    marketplace.cancel_sell_order(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        vec![token_ids[1]],
+    );
+
+    let bob = env.next_user();
+    for (entry_point, owner_args) in vec![
+        (
+            "get_sell_orders_by_seller",
+            runtime_args! { "seller" => Address::from(ali) },
+        ),
+        (
+            "get_sell_orders_by_collection",
+            runtime_args! { "collection" => nft.contract_hash().to_formatted_string() },
+        ),
+    ] {
+        let page = |offset: u64| {
+            let mut args = owner_args.clone();
+            args.insert("offset", offset).unwrap();
+            args.insert("limit", 2u64).unwrap();
+            call_getter::<Vec<SellOrder>>(&env, &marketplace, bob, entry_point, args)
+                .iter()
+                .map(|order| order.id)
+                .collect::<Vec<u64>>()
+        };
+        assert_eq!(page(0), vec![ids[0], ids[3]]);
+        assert_eq!(page(2), vec![ids[2]]);
+        assert!(page(3).is_empty());
+    }
+}
+
+#[test]
+fn should_create_sell_order_and_cancel() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    let nft = test_context.nft;
+    let marketplace = test_context.marketplace;
+    nft.mint_one(owner, user, token_id, token_meta);
+
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let pay_token: Option<String> = None;
+    let price = U256::one();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        pay_token,
+        None,
+        None,
+        true,
+    );
+    let order_id = marketplace.sell_order_of(nft.contract_hash(), token_id).id;
+
+    marketplace.cancel_sell_order(
+        user,
+        nft.contract_hash().to_formatted_string(),
+        vec![token_id],
+    );
+
+    // the canceled order is kept for auditing
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(user));
+    assert_eq!(
+        marketplace.sell_order_by_id(order_id).status,
+        OrderStatus::Canceled
+    );
+}
+
+#[test]
+fn should_sweep_expired_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    let nft = test_context.nft;
+    let marketplace = test_context.marketplace;
+    nft.mint_one(owner, user, token_id, token_meta);
+
+    nft.approve(
+        user,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let now = SystemTime::now();
+    let end_time = now
+        .checked_add(Duration::from_secs(3600))
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let pay_token: Option<String> = None;
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, U256::one());
+    marketplace.create_sell_order(
+        user,
+        0u64,
+        Some(end_time),
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        pay_token,
+        None,
+        None,
+        true,
+    );
+    assert_eq!(
+        nft.owner_of(token_id).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+
+    marketplace.sweep_expired_sell_orders(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        vec![token_id],
+        now.checked_add(Duration::from_secs(7200)).unwrap(),
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(user));
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+// lists `token_id` of `seller` for USDT within `[start_time, end_time)`
+fn list_timed_token(
+    test_context: &TestContext,
+    owner: AccountHash,
+    seller: AccountHash,
+    token_id: TokenId,
+    start_time: u64,
+    end_time: Option<u64>,
+) {
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    nft.mint_one(owner, seller, token_id, meta::red_dragon());
+    nft.approve(
+        seller,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, U256::from(100u8));
+    marketplace.create_sell_order(
+        seller,
+        start_time,
+        end_time,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(test_context.erc20.contract_hash().to_formatted_string()),
+        None,
+        None,
+        true,
+    );
+}
+
+#[test]
+fn should_skip_live_sell_orders_when_sweeping() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let now = SystemTime::now();
+    let expiring = TokenId::zero();
+    let live = TokenId::one();
+    let unlisted = TokenId::from(2u8);
+    list_timed_token(
+        &test_context,
+        owner,
+        user,
+        expiring,
+        0u64,
+        Some(unix_time(now + Duration::from_secs(3600))),
+    );
+    list_timed_token(&test_context, owner, user, live, 0u64, None);
+
+    let nft = &test_context.nft;
+    let marketplace = &test_context.marketplace;
+    let expiring_id = marketplace.sell_order_of(nft.contract_hash(), expiring).id;
+    marketplace.sweep_expired_sell_orders(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        vec![expiring, live, unlisted],
+        now + Duration::from_secs(7200),
+    );
+
+    assert_eq!(nft.owner_of(expiring).unwrap(), Key::from(user));
+    assert_eq!(
+        nft.owner_of(live).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+    assert_eq!(
+        marketplace.sell_order_by_id(expiring_id).status,
+        OrderStatus::Expired
+    );
+    assert_eq!(
+        marketplace.sell_order_of(nft.contract_hash(), live).status,
+        OrderStatus::Pending
+    );
+}
+
+#[test]
+fn should_not_sweep_expired_sell_orders_as_non_admin() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let now = SystemTime::now();
+    let token_id = TokenId::zero();
+    list_timed_token(
+        &test_context,
+        owner,
+        user,
+        token_id,
+        0u64,
+        Some(unix_time(now + Duration::from_secs(3600))),
+    );
+
+    let nft = &test_context.nft;
+    test_context
+        .marketplace
+        .sweep_expired_sell_orders_with_error(
+            user,
+            nft.contract_hash().to_formatted_string(),
+            vec![token_id],
+            now + Duration::from_secs(7200),
+            ApiError::PermissionDenied,
+        );
+    assert_eq!(
+        nft.owner_of(token_id).unwrap(),
+        Key::from(test_context.marketplace.contract_package_hash())
+    );
+}
+
+#[test]
+fn should_not_buy_sell_order_before_start_time() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let now = SystemTime::now();
+    let token_id = TokenId::zero();
+    list_timed_token(
+        &test_context,
+        owner,
+        user,
+        token_id,
+        unix_time(now + Duration::from_secs(3600)),
+        None,
+    );
+
+    let bob = env.next_user();
+    let marketplace = &test_context.marketplace;
+    let usdt = &test_context.erc20;
+    let price = U256::from(100u8);
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order_with_error(
+        bob,
+        test_context.nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+        now,
+        Error::NotStartedOrder,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+}
+
+#[test]
+fn should_not_buy_expired_sell_order() {
+    let (env, test_context, owner) = deploy();
+    let user = env.next_user();
+    let now = SystemTime::now();
+    let token_id = TokenId::zero();
+    list_timed_token(
+        &test_context,
+        owner,
+        user,
+        token_id,
+        0u64,
+        Some(unix_time(now + Duration::from_secs(3600))),
+    );
+
+    let bob = env.next_user();
+    let marketplace = &test_context.marketplace;
+    let usdt = &test_context.erc20;
+    let price = U256::from(100u8);
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order_with_error(
+        bob,
+        test_context.nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+        now + Duration::from_secs(7200),
+        Error::ExpiredOrder,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+}
+
+#[test]
+fn should_create_buy_order_and_cancel() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), offer_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        offer_amount,
+    );
+    let mut bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
+    assert_eq!(bob_usdt_balance, offer_amount);
+
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        offer_amount,
+    );
+    bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
+    assert_eq!(bob_usdt_balance, U256::zero());
+
+    marketplace.cancel_buy_order(bob, nft.contract_hash().to_formatted_string(), token_id);
+}
+
+#[test]
+fn should_update_buy_order_price() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    let raised_amount = U256::from(120u8).checked_mul(U256::exp10(9)).unwrap();
+    let lowered_amount = U256::from(60u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), raised_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        raised_amount,
+    );
+
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        offer_amount,
+    );
+    assert_eq!(
+        usdt.balance_of(Key::from(bob)).unwrap(),
+        raised_amount - offer_amount
+    );
+
+    // raising the bid pulls the difference into escrow
+    marketplace.update_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        raised_amount,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+
+    // lowering it refunds the difference
+    marketplace.update_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        lowered_amount,
+    );
+    assert_eq!(
+        usdt.balance_of(Key::from(bob)).unwrap(),
+        raised_amount - lowered_amount
+    );
+    let bids = marketplace.buy_order_of(nft.contract_hash(), token_id);
+    assert_eq!(bids.get(&Address::from(bob)).unwrap().price, lowered_amount);
+
+    // a zero price is rejected, bids are withdrawn through cancel_buy_order
+    marketplace.update_buy_order_with_condition(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        U256::zero(),
+        false,
+    );
+    assert_eq!(
+        usdt.balance_of(Key::from(bob)).unwrap(),
+        raised_amount - lowered_amount
+    );
+    let bids = marketplace.buy_order_of(nft.contract_hash(), token_id);
+    assert_eq!(bids.get(&Address::from(bob)).unwrap().price, lowered_amount);
+}
+
+#[test]
+fn should_update_buy_order_cspr_price() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+
+    let bob = env.next_user();
+    let cspr = |amount: u8| U512::from(amount).checked_mul(U512::exp10(9)).unwrap();
+    let motes = |price: u8| U256::from(price).checked_mul(U256::exp10(9)).unwrap();
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_ORDER_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "entrypoint" => "create_buy_order_cspr".to_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "token_id" => token_id,
+            "amount" => cspr(90),
+            "additional_recipient" => Option::<Address>::None,
+            "end_time" => Option::<u64>::None
+        },
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(90));
+
+    let update_args = |price: u8, amount: u8| {
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "token_id" => token_id,
+            "price" => motes(price),
+            "amount" => cspr(amount)
+        }
+    };
+    let bid_price = || {
+        marketplace
+            .buy_order_of(nft.contract_hash(), token_id)
+            .get(&Address::from(bob))
+            .unwrap()
+            .price
+    };
+
+    // raising the bid needs a top-up of the difference
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_UPDATE_BUY_ORDER_CSPR_WASM)),
+        update_args(150, 10),
+        SystemTime::now(),
+        Error::InsufficientBalance,
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(90));
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_UPDATE_BUY_ORDER_CSPR_WASM)),
+        update_args(120, 30),
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(120));
+    assert_eq!(bid_price(), motes(120));
+
+    // lowering it refunds the difference, no CSPR is sent along
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_UPDATE_BUY_ORDER_CSPR_WASM)),
+        update_args(60, 0),
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(60));
+    assert_eq!(bid_price(), motes(60));
+
+    // a top-up beyond the new price is refunded as well
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_UPDATE_BUY_ORDER_CSPR_WASM)),
+        update_args(70, 20),
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(70));
+    assert_eq!(bid_price(), motes(70));
+
+    // a zero price is rejected, bids are withdrawn through cancel_buy_order
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_UPDATE_BUY_ORDER_CSPR_WASM)),
+        update_args(0, 0),
+        SystemTime::now(),
+        Error::InvalidPrice,
+    );
+    assert_eq!(marketplace.purse_balance(), cspr(70));
+}
+
+#[test]
+fn should_reclaim_expired_buy_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), offer_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        offer_amount,
+    );
+
+    let now = SystemTime::now();
+    let end_time = now
+        .checked_add(Duration::from_secs(3600))
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        Some(end_time),
+        offer_amount,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+
+    // anyone can reclaim once the bid has expired
+    marketplace.reclaim_expired_buy_orders(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        vec![token_id],
+        now.checked_add(Duration::from_secs(7200)).unwrap(),
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), offer_amount);
+    assert!(marketplace
+        .buy_order_of(nft.contract_hash(), token_id)
+        .is_empty());
+}
+
+#[test]
+fn should_create_buy_order_and_accept() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(90u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), offer_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        offer_amount,
+    );
+    let mut bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
+    assert_eq!(bob_usdt_balance, offer_amount);
+
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        offer_amount,
+    );
+    bob_usdt_balance = usdt.balance_of(Key::from(bob)).unwrap();
+    assert_eq!(bob_usdt_balance, U256::zero());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    marketplace.accept_buy_order(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Address::from(bob),
+    );
+    let token_owner = nft.owner_of(token_id).unwrap();
+    assert_eq!(token_owner, Key::from(bob));
+}
+
+#[test]
+fn should_accept_collection_offer_and_refund_residue() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+
+    let bob = env.next_user();
+    let price = U256::from(45u8).checked_mul(U256::exp10(9)).unwrap();
+    let escrow = price.checked_mul(U256::from(2u8)).unwrap();
+    usdt.transfer(owner, Key::from(bob), escrow);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), escrow);
+
+    marketplace.create_collection_offer(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        usdt.contract_hash().to_formatted_string(),
+        price,
+        2,
+        None,
+        None,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    marketplace.accept_collection_offer(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Address::from(bob),
+        vec![],
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    // 5% USDT fee
+    assert_eq!(
+        usdt.balance_of(Key::from(ali)).unwrap(),
+        U256::from(4275u32).checked_mul(U256::exp10(7)).unwrap()
+    );
+
+    // the unfilled quantity is refunded
+    let offer = marketplace
+        .collection_offer_of(nft.contract_hash(), Address::from(bob))
+        .unwrap();
+    assert_eq!(offer.quantity, 1);
+    marketplace.cancel_collection_offer(bob, nft.contract_hash().to_formatted_string());
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+    assert!(marketplace
+        .collection_offer_of(nft.contract_hash(), Address::from(bob))
+        .is_none());
+    let closed_offer = marketplace.closed_collection_offer(offer.id);
+    assert_eq!(closed_offer.status, OrderStatus::Canceled);
+    assert_eq!(closed_offer.quantity, 1);
+}
+
+#[test]
+fn should_accept_cspr_collection_offer_and_refund_residue() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+
+    let bob = env.next_user();
+    let offer_args = |amount: U512| {
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "quantity" => 2u32,
+            "end_time" => Option::<u64>::None,
+            "merkle_root" => Option::<String>::None,
+            "amount" => amount
+        }
+    };
+    let escrow = U512::from(90u8).checked_mul(U512::exp10(9)).unwrap();
+
+    // the escrow must split into an equal price per token
+    env.run_with_error(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_COLLECTION_OFFER_CSPR_WASM)),
+        offer_args(escrow + 1),
+        SystemTime::now(),
+        Error::InvalidQuantity,
+    );
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+
+    env.run(
+        bob,
+        DeploySource::Code(PathBuf::from(PRE_COLLECTION_OFFER_CSPR_WASM)),
+        offer_args(escrow),
+    );
+    assert_eq!(marketplace.purse_balance(), escrow);
+    let offer = marketplace
+        .collection_offer_of(nft.contract_hash(), Address::from(bob))
+        .unwrap();
+    assert_eq!(offer.pay_token, None);
+    assert_eq!(
+        offer.price,
+        U256::from(45u8).checked_mul(U256::exp10(9)).unwrap()
+    );
+
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let fee_wallet_balance = env.balance_of(owner);
+    marketplace.accept_collection_offer(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Address::from(bob),
+        vec![],
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    // 10% CSPR fee of the 45 CSPR price
+    assert_eq!(
+        env.balance_of(owner) - fee_wallet_balance,
+        U512::from(45u8).checked_mul(U512::exp10(8)).unwrap()
+    );
+    let residue = U512::from(45u8).checked_mul(U512::exp10(9)).unwrap();
+    assert_eq!(marketplace.purse_balance(), residue);
+
+    // the unfilled quantity is refunded from the deposit purse
+    marketplace.cancel_collection_offer(bob, nft.contract_hash().to_formatted_string());
+    assert_eq!(marketplace.purse_balance(), U512::zero());
+    let closed_offer = marketplace.closed_collection_offer(offer.id);
+    assert_eq!(closed_offer.status, OrderStatus::Canceled);
+    assert_eq!(closed_offer.quantity, 1);
+}
+
+#[test]
+fn should_accept_trait_offer_with_merkle_proof() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::one();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+
+    // keccak256 tree over the token ids "0" and "1"
+    let merkle_root =
+        "0b4aa17bff8fc189efb37609ac5ea9fca0df4c834a6fbac74b24c8119c40fef2".to_string();
+    let proof = vec![(
+        "044852b2a670ade5407e78fb2863c51de9fcb96542a07186fe3aeda6bb8a116d".to_string(),
+        0u8,
+    )];
+
+    let bob = env.next_user();
+    let price = U256::from(45u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    // the root must be 32 hex encoded bytes
+    marketplace.create_collection_offer_with_condition(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        usdt.contract_hash().to_formatted_string(),
+        price,
+        1,
+        None,
+        Some(merkle_root[..62].to_string()),
+        false,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), price);
+    marketplace.create_collection_offer(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        usdt.contract_hash().to_formatted_string(),
+        price,
+        1,
+        None,
+        Some(merkle_root),
+    );
+
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let offer_id = marketplace
+        .collection_offer_of(nft.contract_hash(), Address::from(bob))
+        .unwrap()
+        .id;
+    // malformed proofs revert instead of trapping
+    let mut unknown_position = proof.clone();
+    unknown_position[0].1 = 7;
+    let mut not_hex = proof.clone();
+    not_hex[0].0 = "0x".to_string();
+    for malformed_proof in [unknown_position, not_hex] {
+        marketplace.accept_collection_offer_with_error(
+            ali,
+            nft.contract_hash().to_formatted_string(),
+            token_id,
+            Address::from(bob),
+            malformed_proof,
+            Error::InvalidProof,
+        );
+    }
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(ali));
+
+    marketplace.accept_collection_offer(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Address::from(bob),
+        proof,
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    // the filled offer leaves the book and is kept by id
+    assert!(marketplace
+        .collection_offer_of(nft.contract_hash(), Address::from(bob))
+        .is_none());
+    assert_eq!(
+        marketplace.closed_collection_offer(offer_id).status,
+        OrderStatus::Completed
+    );
+}
+
+#[test]
+fn should_create_auction_and_settle() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let now = SystemTime::now();
+    let end_time = now
+        .checked_add(Duration::from_secs(3600))
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let reserve_price = U256::from(10u8).checked_mul(U256::exp10(9)).unwrap();
+    marketplace.create_auction(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Some(usdt.contract_hash().to_formatted_string()),
+        reserve_price,
+        U256::exp10(9),
+        0u64,
+        end_time,
+        0u64,
+    );
+
+    let bob = env.next_user();
+    let bob_bid = reserve_price;
+    usdt.transfer(owner, Key::from(bob), bob_bid);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), bob_bid);
+    marketplace.place_bid(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        bob_bid,
+    );
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), U256::zero());
+
+    let carol = env.next_user();
+    let carol_bid = U256::from(20u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(carol), carol_bid);
+    usdt.approve(
+        carol,
+        Key::from(marketplace.contract_package_hash()),
+        carol_bid,
+    );
+    marketplace.place_bid(
+        carol,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        carol_bid,
+    );
+    // outbid bidder is refunded
+    assert_eq!(usdt.balance_of(Key::from(bob)).unwrap(), bob_bid);
+
+    let auction_id = marketplace.auction_of(nft.contract_hash(), token_id).id;
+    marketplace.settle_auction(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        now.checked_add(Duration::from_secs(7200)).unwrap(),
+    );
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(carol));
+    // 5% fee goes to the fee wallet
+    assert_eq!(
+        usdt.balance_of(Key::from(ali)).unwrap(),
+        U256::from(19u8).checked_mul(U256::exp10(9)).unwrap()
+    );
+    let auction = marketplace.closed_auction(auction_id);
+    assert_eq!(auction.status, OrderStatus::Completed);
+    assert_eq!(auction.highest_bidder, Some(Address::from(carol)));
+}
+
+#[test]
+fn should_cancel_auction_without_bids() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let end_time = SystemTime::now()
+        .checked_add(Duration::from_secs(3600))
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    marketplace.create_auction(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        U256::exp10(9),
+        U256::exp10(9),
+        0u64,
+        end_time,
+        0u64,
+    );
+    let auction_id = marketplace.auction_of(nft.contract_hash(), token_id).id;
+    marketplace.cancel_auction(ali, nft.contract_hash().to_formatted_string(), token_id);
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(ali));
+    assert_eq!(
+        marketplace.closed_auction(auction_id).status,
+        OrderStatus::Canceled
+    );
+}
+
+#[test]
+fn should_extend_auction_on_late_bid() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    let extension_window = 600u64;
+    marketplace.set_max_auction_extension(owner, extension_window);
+
+    // auction ends before the extension window elapses, so any bid is a late bid
+    let end_time = SystemTime::now()
+        .checked_add(Duration::from_secs(300))
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let reserve_price = U256::exp10(9);
+    marketplace.create_auction(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Some(usdt.contract_hash().to_formatted_string()),
+        reserve_price,
+        U256::exp10(9),
+        0u64,
+        end_time,
+        extension_window,
+    );
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), reserve_price);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        reserve_price,
+    );
+    marketplace.place_bid(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        reserve_price,
+    );
+
+    let auction = marketplace.auction_of(nft.contract_hash(), token_id);
+    assert!(auction.end_time > end_time);
+}
+
+// events written by the last deploy, as stored by the marketplace
+fn last_events(env: &TestEnv, event_type: &str) -> Vec<BTreeMap<String, String>> {
+    env.last_written_values::<BTreeMap<String, String>>()
+        .into_iter()
+        .filter(|event| event.get("event_type").map(String::as_str) == Some(event_type))
+        .collect()
+}
+
+#[test]
+fn should_extend_auction_up_to_default_cap() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    // no cap set by the admin, the default of 600 applies
+    let end_time = unix_time(SystemTime::now() + Duration::from_secs(300));
+    let reserve_price = U256::exp10(9);
+    marketplace.create_auction_with_error(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Some(usdt.contract_hash().to_formatted_string()),
+        reserve_price,
+        U256::exp10(9),
+        0u64,
+        end_time,
+        601u64,
+        Error::InvalidExtensionWindow,
+    );
+    marketplace.create_auction(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Some(usdt.contract_hash().to_formatted_string()),
+        reserve_price,
+        U256::exp10(9),
+        0u64,
+        end_time,
+        600u64,
+    );
+
+    let bob = env.next_user();
+    usdt.transfer(owner, Key::from(bob), reserve_price);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        reserve_price,
+    );
+    marketplace.place_bid(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        reserve_price,
+    );
+
+    let auction = marketplace.auction_of(nft.contract_hash(), token_id);
+    assert!(auction.end_time > end_time);
+    let events = last_events(&env, "AuctionExtended");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], auction.id.to_string());
+    assert_eq!(events[0]["end_time"], auction.end_time.to_string());
+}
+
+#[test]
+fn should_not_create_auction_above_max_extension() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+
+    marketplace.set_max_auction_extension(owner, 60u64);
+    marketplace.create_auction_with_error(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        U256::exp10(9),
+        U256::exp10(9),
+        0u64,
+        unix_time(SystemTime::now() + Duration::from_secs(3600)),
+        120u64,
+        Error::InvalidExtensionWindow,
+    );
+    // the token is not escrowed
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(ali));
+}
+
+#[test]
+fn should_pay_royalty_on_accept_buy_order() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let artist = env.next_user();
+    let dao = env.next_user();
+    marketplace.set_collection_admin(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        Address::from(artist),
+    );
+    // 2.5% royalty set by the collection admin, split between the artist and a DAO
+    marketplace.set_royalty(
+        artist,
+        nft.contract_hash().to_formatted_string(),
+        vec![(Address::from(artist), 150), (Address::from(dao), 100)],
+    );
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    let token_meta = meta::red_dragon();
+    nft.mint_one(owner, ali, token_id, token_meta);
+
+    let bob = env.next_user();
+    let offer_amount = U256::from(100u8).checked_mul(U256::exp10(9)).unwrap();
+    usdt.transfer(owner, Key::from(bob), offer_amount);
+    usdt.approve(
+        bob,
+        Key::from(marketplace.contract_package_hash()),
+        offer_amount,
+    );
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        offer_amount,
+    );
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    marketplace.accept_buy_order(
+        ali,
+        nft.contract_hash().to_formatted_string(),
         token_id,
         Address::from(bob),
     );
-    let token_owner = nft.owner_of(token_id).unwrap();
-    assert_eq!(token_owner, Key::from(bob));
+
+    assert_eq!(
+        usdt.balance_of(Key::from(artist)).unwrap(),
+        U256::from(15u8).checked_mul(U256::exp10(8)).unwrap()
+    );
+    assert_eq!(usdt.balance_of(Key::from(dao)).unwrap(), U256::exp10(9));
+    // seller receives the price minus 5% fee and 2.5% royalty
+    assert_eq!(
+        usdt.balance_of(Key::from(ali)).unwrap(),
+        U256::from(925u16).checked_mul(U256::exp10(8)).unwrap()
+    );
+}
+
+#[test]
+fn should_split_rounding_dust_without_losing_funds() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let usdt = test_context.erc20;
+
+    let artist = env.next_user();
+    let dao = env.next_user();
+    marketplace.set_collection_admin(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        Address::from(artist),
+    );
+    marketplace.set_royalty(
+        artist,
+        nft.contract_hash().to_formatted_string(),
+        vec![(Address::from(artist), 150), (Address::from(dao), 100)],
+    );
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+
+    // neither the 5% fee nor any royalty share of 999 is a whole amount
+    let bob = env.next_user();
+    let price = U256::from(999u16);
+    usdt.transfer(owner, Key::from(bob), price);
+    usdt.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.create_buy_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        None,
+        usdt.contract_hash().to_formatted_string(),
+        None,
+        price,
+    );
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let fee_wallet_before = usdt.balance_of(Key::from(owner)).unwrap();
+    marketplace.accept_buy_order(
+        ali,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        Address::from(bob),
+    );
+
+    let fee = usdt.balance_of(Key::from(owner)).unwrap() - fee_wallet_before;
+    let artist_share = usdt.balance_of(Key::from(artist)).unwrap();
+    let dao_share = usdt.balance_of(Key::from(dao)).unwrap();
+    let seller_share = usdt.balance_of(Key::from(ali)).unwrap();
+    assert_eq!(fee, U256::from(49u8));
+    // 14.985 and 9.99 round down, the dust of the 24.975 total goes to the first share
+    assert_eq!(artist_share, U256::from(15u8));
+    assert_eq!(dao_share, U256::from(9u8));
+    assert_eq!(fee + artist_share + dao_share + seller_share, price);
+    assert_eq!(
+        usdt.balance_of(Key::from(marketplace.contract_package_hash()))
+            .unwrap(),
+        U256::zero()
+    );
+}
+
+#[test]
+fn should_buy_sell_order_with_cep18_token() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+
+    let supply = U256::from(10_000u64).checked_mul(U256::exp10(9)).unwrap();
+    let cep18 = CEP18Instance::new(&env, "CSPX", owner, "CSPX", 9, supply);
+    marketplace.set_acceptable_token(owner, cep18.contract_hash().to_formatted_string(), 500);
+    marketplace.set_token_standard(owner, cep18.contract_hash().to_formatted_string(), 1);
+
+    let artist = env.next_user();
+    marketplace.set_collection_admin(
+        owner,
+        nft.contract_hash().to_formatted_string(),
+        Address::from(artist),
+    );
+    marketplace.set_royalty(
+        artist,
+        nft.contract_hash().to_formatted_string(),
+        vec![(Address::from(artist), 250)],
+    );
+
+    let ali = env.next_user();
+    let token_id = TokenId::zero();
+    nft.mint_one(owner, ali, token_id, meta::red_dragon());
+    nft.approve(
+        ali,
+        Key::from(marketplace.contract_package_hash()),
+        vec![token_id],
+    );
+    let price = U256::from(100u8).checked_mul(U256::exp10(9)).unwrap();
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(token_id, price);
+    marketplace.create_sell_order(
+        ali,
+        0u64,
+        None,
+        nft.contract_hash().to_formatted_string(),
+        tokens,
+        Some(cep18.contract_hash().to_formatted_string()),
+        None,
+        None,
+        true,
+    );
+
+    let bob = env.next_user();
+    cep18.transfer(owner, Key::from(bob), price);
+    cep18.approve(bob, Key::from(marketplace.contract_package_hash()), price);
+    marketplace.buy_sell_order(
+        bob,
+        nft.contract_hash().to_formatted_string(),
+        token_id,
+        price,
+        None,
+        vec![],
+    );
+
+    assert_eq!(nft.owner_of(token_id).unwrap(), Key::from(bob));
+    assert_eq!(cep18.balance_of(Key::from(bob)), U256::zero());
+    // the allowance is consumed by `transfer_from`
+    assert_eq!(
+        cep18.allowance(
+            Key::from(bob),
+            Key::from(marketplace.contract_package_hash())
+        ),
+        U256::zero()
+    );
+    // 5% fee and 2.5% royalty
+    assert_eq!(
+        cep18.balance_of(Key::from(ali)),
+        U256::from(925u16).checked_mul(U256::exp10(8)).unwrap()
+    );
+    assert_eq!(
+        cep18.balance_of(Key::from(artist)),
+        U256::from(25u8).checked_mul(U256::exp10(8)).unwrap()
+    );
+    assert_eq!(
+        cep18.balance_of(Key::from(owner)),
+        supply - price + U256::from(5u8).checked_mul(U256::exp10(9)).unwrap()
+    );
+}
+
+// lists CEP-78 token 0 of `seller` for 100 CSPR, `artist` administers the collection
+fn list_cep78_token(
+    env: &TestEnv,
+    marketplace: &MarketplaceInstance,
+    owner: AccountHash,
+    artist: AccountHash,
+    seller: AccountHash,
+) -> CEP78Instance {
+    let nft = CEP78Instance::new(env, "cep78", owner);
+    let collection = nft.contract_hash().to_formatted_string();
+    // CEP78Ordinal
+    marketplace.set_collection_standard(owner, collection.clone(), 1);
+    marketplace.set_collection_admin(owner, collection.clone(), Address::from(artist));
+
+    nft.mint(owner, seller, 0);
+    nft.approve(seller, Key::from(marketplace.contract_package_hash()), 0);
+    let mut tokens: BTreeMap<TokenId, U256> = BTreeMap::new();
+    tokens.insert(
+        TokenId::zero(),
+        U256::from(100u8).checked_mul(U256::exp10(9)).unwrap(),
+    );
+    marketplace.create_sell_order(
+        seller, 0u64, None, collection, tokens, None, None, None, true,
+    );
+    nft
+}
+
+fn buy_cep78_token(
+    env: &TestEnv,
+    marketplace: &MarketplaceInstance,
+    nft: &CEP78Instance,
+    buyer: AccountHash,
+    success: bool,
+) {
+    let additional_recipient: Option<Address> = None;
+    env.run_with_condition(
+        buyer,
+        DeploySource::Code(PathBuf::from(PER_BUY_SELL_ORDER_CSPR_WASM)),
+        runtime_args! {
+            "marketplace_contract" => marketplace.contract_hash().to_formatted_string(),
+            "collection" => nft.contract_hash().to_formatted_string(),
+            "token_id" => TokenId::zero(),
+            "amount" => U512::from(100u8).checked_mul(U512::exp10(9)).unwrap(),
+            "additional_recipient" => additional_recipient,
+            "proof" => Vec::<(String, u8)>::new()
+        },
+        success,
+    );
+}
+
+#[test]
+fn should_pay_royalty_from_collection_royalty_info() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let artist = env.next_user();
+    let dao = env.next_user();
+    let seller = env.next_user();
+    let nft = list_cep78_token(&env, &marketplace, owner, artist, seller);
+    let collection = nft.contract_hash().to_formatted_string();
+    marketplace.set_royalty(artist, collection.clone(), vec![(Address::from(dao), 100)]);
+    marketplace.set_royalty_info_supported(artist, collection, true);
+    // 5% to the artist, the local registry is ignored
+    nft.set_royalty_info(owner, artist, 500);
+
+    let artist_balance = env.balance_of(artist);
+    let dao_balance = env.balance_of(dao);
+    let seller_balance = env.balance_of(seller);
+    let buyer = env.next_user();
+    buy_cep78_token(&env, &marketplace, &nft, buyer, true);
+
+    assert_eq!(nft.owner_of(0).unwrap(), Key::from(buyer));
+    assert_eq!(
+        env.balance_of(artist) - artist_balance,
+        U512::from(5u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(env.balance_of(dao), dao_balance);
+    // seller receives the price minus 10% fee and 5% royalty
+    assert_eq!(
+        env.balance_of(seller) - seller_balance,
+        U512::from(85u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+}
+
+#[test]
+fn should_fall_back_to_royalty_registry() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let artist = env.next_user();
+    let dao = env.next_user();
+    let seller = env.next_user();
+    let nft = list_cep78_token(&env, &marketplace, owner, artist, seller);
+    let collection = nft.contract_hash().to_formatted_string();
+    marketplace.set_royalty(artist, collection, vec![(Address::from(dao), 100)]);
+    // not queried until the collection is marked as supporting royalty_info
+    nft.set_royalty_info(owner, artist, 500);
+
+    let artist_balance = env.balance_of(artist);
+    let dao_balance = env.balance_of(dao);
+    let seller_balance = env.balance_of(seller);
+    let buyer = env.next_user();
+    buy_cep78_token(&env, &marketplace, &nft, buyer, true);
+
+    assert_eq!(nft.owner_of(0).unwrap(), Key::from(buyer));
+    assert_eq!(env.balance_of(artist), artist_balance);
+    assert_eq!(
+        env.balance_of(dao) - dao_balance,
+        U512::from(1u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+    assert_eq!(
+        env.balance_of(seller) - seller_balance,
+        U512::from(89u8).checked_mul(U512::exp10(9)).unwrap()
+    );
+}
+
+#[test]
+fn should_reject_royalty_info_above_max_royalty() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let artist = env.next_user();
+    let seller = env.next_user();
+    let nft = list_cep78_token(&env, &marketplace, owner, artist, seller);
+    marketplace.set_royalty_info_supported(artist, nft.contract_hash().to_formatted_string(), true);
+    // 30% is above the 25% cap
+    nft.set_royalty_info(owner, artist, 3000);
+
+    let buyer = env.next_user();
+    buy_cep78_token(&env, &marketplace, &nft, buyer, false);
+
+    assert_eq!(
+        nft.owner_of(0).unwrap(),
+        Key::from(marketplace.contract_package_hash())
+    );
+    let order = marketplace.sell_order_of(nft.contract_hash(), TokenId::zero());
+    assert_eq!(order.status, OrderStatus::Pending);
+}
+
+#[test]
+fn should_restrict_royalty_info_supported_to_collection_admin() {
+    let (env, test_context, owner) = deploy();
+    let marketplace = test_context.marketplace;
+    let nft = test_context.nft;
+    let collection = nft.contract_hash().to_formatted_string();
+    let artist = env.next_user();
+    let stranger = env.next_user();
+    marketplace.set_collection_admin(owner, collection.clone(), Address::from(artist));
+
+    marketplace.set_royalty_info_supported_with_condition(
+        stranger,
+        collection.clone(),
+        true,
+        false,
+    );
+    assert!(!marketplace.royalty_info_supported(nft.contract_hash()));
+
+    marketplace.set_royalty_info_supported(artist, collection.clone(), true);
+    assert!(marketplace.royalty_info_supported(nft.contract_hash()));
+
+    // the marketplace admin keeps access
+    marketplace.set_royalty_info_supported(owner, collection, false);
+    assert!(!marketplace.royalty_info_supported(nft.contract_hash()));
 }
 
 #[test]
@@ -1,8 +1,8 @@
 use std::time::SystemTime;
 
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, CLTyped, ContractHash, ContractPackageHash,
-    RuntimeArgs,
+    account::AccountHash, bytesrepr::FromBytes, ApiError, CLTyped, ContractHash,
+    ContractPackageHash, RuntimeArgs,
 };
 
 use crate::{utils::DeploySource, TestEnv};
@@ -109,4 +109,20 @@ impl TestContract {
         self.env
             .run_with_time(sender, session_code, session_args, time);
     }
+
+    pub fn call_contract_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        time: SystemTime,
+        error: E,
+    ) {
+        let session_code = DeploySource::ByHash {
+            hash: self.contract_hash(),
+            method: entry_point.to_string(),
+        };
+        self.env
+            .run_with_error(sender, session_code, session_args, time, error);
+    }
 }
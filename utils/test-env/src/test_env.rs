@@ -7,10 +7,14 @@ use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey,
+    account::AccountHash, bytesrepr::FromBytes, ApiError, CLTyped, Key, PublicKey, RuntimeArgs,
+    SecretKey, U512,
 };
 
-use crate::utils::{deploy, fund_account, query, query_dictionary_item, DeploySource};
+use crate::utils::{
+    deploy, deploy_with_error, fund_account, last_written_values, query, query_dictionary_item,
+    DeploySource,
+};
 
 #[derive(Clone)]
 pub struct TestEnv {
@@ -94,14 +98,59 @@ impl TestEnv {
         )
     }
 
+    pub fn run_with_error<E: Into<ApiError>>(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+        time: SystemTime,
+        error: E,
+    ) {
+        let since_the_epoch: u64 = time
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        deploy_with_error(
+            &mut self.state.lock().unwrap().builder,
+            &sender,
+            &session_code,
+            session_args,
+            Some(since_the_epoch),
+            error.into(),
+        )
+    }
+
     pub fn get_account(&self, account_hash: AccountHash) -> Option<casper_types::account::Account> {
         self.state.lock().unwrap().builder.get_account(account_hash)
     }
 
+    pub fn balance_of(&self, account_hash: AccountHash) -> U512 {
+        let state = self.state.lock().unwrap();
+        let main_purse = state
+            .builder
+            .get_account(account_hash)
+            .unwrap()
+            .main_purse();
+        state.builder.get_purse_balance(main_purse)
+    }
+
     pub fn next_user(&self) -> AccountHash {
         self.state.lock().unwrap().next_user()
     }
 
+    // funds a new account for a key that is not one of the default ed25519 users
+    pub fn add_user(&self, public_key: &PublicKey) -> AccountHash {
+        let account_hash = AccountHash::from(public_key);
+        self.state
+            .lock()
+            .unwrap()
+            .builder
+            .exec(fund_account(&account_hash))
+            .expect_success()
+            .commit();
+        account_hash
+    }
+
     pub fn query_dictionary<T: CLTyped + FromBytes>(
         &self,
         contract_hash: [u8; 32],
@@ -137,6 +186,10 @@ impl TestEnv {
             .query_account_named_key(account, path)
     }
 
+    pub fn last_written_values<T: CLTyped + FromBytes>(&self) -> Vec<T> {
+        last_written_values(&self.state.lock().unwrap().builder)
+    }
+
     pub fn get_account_named_key(&self, account_hash: AccountHash, key: String) -> Key {
         self.state
             .lock()
@@ -4,11 +4,17 @@ use casper_engine_test_support::{
     DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
     DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
 };
-use casper_execution_engine::core::engine_state::execute_request::ExecuteRequest;
+use casper_execution_engine::{
+    core::{
+        engine_state::{self, execute_request::ExecuteRequest},
+        execution,
+    },
+    shared::transform::Transform,
+};
 
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, runtime_args, system::mint, CLTyped, ContractHash,
-    Key, RuntimeArgs, StoredValue, U512,
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, system::mint, ApiError, CLTyped,
+    ContractHash, Key, RuntimeArgs, StoredValue, U512,
 };
 
 pub fn query<T: FromBytes + CLTyped>(
@@ -81,6 +87,41 @@ pub fn deploy(
     .commit();
 }
 
+// Runs the deploy expecting it to revert with exactly `error`
+pub fn deploy_with_error(
+    builder: &mut InMemoryWasmTestBuilder,
+    deployer: &AccountHash,
+    source: &DeploySource,
+    args: RuntimeArgs,
+    block_time: Option<u64>,
+    error: ApiError,
+) {
+    deploy(builder, deployer, source, args, false, block_time);
+    match builder.get_error() {
+        Some(engine_state::Error::Exec(execution::Error::Revert(api_error))) => {
+            assert_eq!(api_error, error)
+        }
+        other => panic!("expected revert with {:?}, got {:?}", error, other),
+    }
+}
+
+// Values of type `T` written by the last committed deploy
+pub fn last_written_values<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder) -> Vec<T> {
+    builder
+        .get_transforms()
+        .last()
+        .map(|transforms| {
+            transforms
+                .iter()
+                .filter_map(|(_, transform)| match transform {
+                    Transform::Write(StoredValue::CLValue(value)) => value.clone().into_t().ok(),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub fn query_dictionary_item(
     builder: &InMemoryWasmTestBuilder,
     key: Key,